@@ -0,0 +1,4 @@
+DROP INDEX event_logs_series_id_key;
+DROP INDEX participants_series_game_player_key;
+DROP INDEX series_series_id_key;
+ALTER TABLE participants DROP COLUMN game;
@@ -0,0 +1,17 @@
+-- Participants are stored per game so later games of a series can live alongside game 1.
+ALTER TABLE participants ADD COLUMN game INTEGER NOT NULL DEFAULT 1;
+
+-- Drop duplicate rows before adding the unique keys. Every table keeps its newest row
+-- (the highest id), which holds the most recently synced data.
+DELETE FROM series
+WHERE id NOT IN (SELECT MAX(id) FROM series GROUP BY series_id);
+
+DELETE FROM participants
+WHERE id NOT IN (SELECT MAX(id) FROM participants GROUP BY series_id, game, player_id);
+
+DELETE FROM event_logs
+WHERE id NOT IN (SELECT MAX(id) FROM event_logs GROUP BY series_id);
+
+CREATE UNIQUE INDEX series_series_id_key ON series (series_id);
+CREATE UNIQUE INDEX participants_series_game_player_key ON participants (series_id, game, player_id);
+CREATE UNIQUE INDEX event_logs_series_id_key ON event_logs (series_id);
@@ -0,0 +1,25 @@
+ALTER TABLE participants DROP COLUMN win;
+ALTER TABLE participants DROP COLUMN team_id;
+ALTER TABLE participants DROP COLUMN team_position;
+ALTER TABLE participants DROP COLUMN secondary_rune_style;
+ALTER TABLE participants DROP COLUMN keystone;
+ALTER TABLE participants DROP COLUMN primary_rune_style;
+ALTER TABLE participants DROP COLUMN item6;
+ALTER TABLE participants DROP COLUMN item5;
+ALTER TABLE participants DROP COLUMN item4;
+ALTER TABLE participants DROP COLUMN item3;
+ALTER TABLE participants DROP COLUMN item2;
+ALTER TABLE participants DROP COLUMN item1;
+ALTER TABLE participants DROP COLUMN item0;
+ALTER TABLE participants DROP COLUMN detector_wards_placed;
+ALTER TABLE participants DROP COLUMN wards_killed;
+ALTER TABLE participants DROP COLUMN wards_placed;
+ALTER TABLE participants DROP COLUMN vision_score;
+ALTER TABLE participants DROP COLUMN total_damage_taken;
+ALTER TABLE participants DROP COLUMN total_damage_dealt_to_champions;
+ALTER TABLE participants DROP COLUMN gold_earned;
+ALTER TABLE participants DROP COLUMN neutral_minions_killed;
+ALTER TABLE participants DROP COLUMN total_minions_killed;
+ALTER TABLE participants DROP COLUMN assists;
+ALTER TABLE participants DROP COLUMN deaths;
+ALTER TABLE participants DROP COLUMN kills;
@@ -0,0 +1,54 @@
+-- Promote the core stats out of stats_json so filters and aggregates can run in SQL.
+ALTER TABLE participants ADD COLUMN kills INTEGER;
+ALTER TABLE participants ADD COLUMN deaths INTEGER;
+ALTER TABLE participants ADD COLUMN assists INTEGER;
+ALTER TABLE participants ADD COLUMN total_minions_killed INTEGER;
+ALTER TABLE participants ADD COLUMN neutral_minions_killed INTEGER;
+ALTER TABLE participants ADD COLUMN gold_earned INTEGER;
+ALTER TABLE participants ADD COLUMN total_damage_dealt_to_champions INTEGER;
+ALTER TABLE participants ADD COLUMN total_damage_taken INTEGER;
+ALTER TABLE participants ADD COLUMN vision_score INTEGER;
+ALTER TABLE participants ADD COLUMN wards_placed INTEGER;
+ALTER TABLE participants ADD COLUMN wards_killed INTEGER;
+ALTER TABLE participants ADD COLUMN detector_wards_placed INTEGER;
+ALTER TABLE participants ADD COLUMN item0 INTEGER;
+ALTER TABLE participants ADD COLUMN item1 INTEGER;
+ALTER TABLE participants ADD COLUMN item2 INTEGER;
+ALTER TABLE participants ADD COLUMN item3 INTEGER;
+ALTER TABLE participants ADD COLUMN item4 INTEGER;
+ALTER TABLE participants ADD COLUMN item5 INTEGER;
+ALTER TABLE participants ADD COLUMN item6 INTEGER;
+ALTER TABLE participants ADD COLUMN primary_rune_style INTEGER;
+ALTER TABLE participants ADD COLUMN keystone INTEGER;
+ALTER TABLE participants ADD COLUMN secondary_rune_style INTEGER;
+ALTER TABLE participants ADD COLUMN team_position TEXT;
+ALTER TABLE participants ADD COLUMN team_id INTEGER;
+ALTER TABLE participants ADD COLUMN win BOOLEAN;
+
+UPDATE participants SET
+    kills = json_extract(stats_json, '$.kills'),
+    deaths = json_extract(stats_json, '$.deaths'),
+    assists = json_extract(stats_json, '$.assists'),
+    total_minions_killed = json_extract(stats_json, '$.totalMinionsKilled'),
+    neutral_minions_killed = json_extract(stats_json, '$.neutralMinionsKilled'),
+    gold_earned = json_extract(stats_json, '$.goldEarned'),
+    total_damage_dealt_to_champions = json_extract(stats_json, '$.totalDamageDealtToChampions'),
+    total_damage_taken = json_extract(stats_json, '$.totalDamageTaken'),
+    vision_score = json_extract(stats_json, '$.visionScore'),
+    wards_placed = json_extract(stats_json, '$.wardsPlaced'),
+    wards_killed = json_extract(stats_json, '$.wardsKilled'),
+    detector_wards_placed = json_extract(stats_json, '$.detectorWardsPlaced'),
+    item0 = json_extract(stats_json, '$.item0'),
+    item1 = json_extract(stats_json, '$.item1'),
+    item2 = json_extract(stats_json, '$.item2'),
+    item3 = json_extract(stats_json, '$.item3'),
+    item4 = json_extract(stats_json, '$.item4'),
+    item5 = json_extract(stats_json, '$.item5'),
+    item6 = json_extract(stats_json, '$.item6'),
+    primary_rune_style = json_extract(stats_json, '$.perks.styles[0].style'),
+    keystone = json_extract(stats_json, '$.perks.styles[0].selections[0].perk'),
+    secondary_rune_style = json_extract(stats_json, '$.perks.styles[1].style'),
+    team_position = NULLIF(json_extract(stats_json, '$.teamPosition'), ''),
+    team_id = json_extract(stats_json, '$.teamId'),
+    win = json_extract(stats_json, '$.win')
+WHERE json_valid(stats_json);
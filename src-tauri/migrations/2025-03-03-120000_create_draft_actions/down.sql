@@ -0,0 +1 @@
+DROP TABLE draft_actions;
@@ -0,0 +1,15 @@
+-- Parsed pick/ban order for each game, derived from event_logs at sync time.
+CREATE TABLE draft_actions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    series_id TEXT NOT NULL,
+    game INTEGER NOT NULL,
+    sequence INTEGER NOT NULL,
+    phase INTEGER NOT NULL,
+    side TEXT NOT NULL,
+    team_name TEXT NOT NULL,
+    action TEXT NOT NULL,
+    champion_name TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX draft_actions_series_game_sequence_key ON draft_actions (series_id, game, sequence);
+CREATE INDEX draft_actions_champion_idx ON draft_actions (champion_name, action);
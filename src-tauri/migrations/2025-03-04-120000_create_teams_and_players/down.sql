@@ -0,0 +1,7 @@
+DROP INDEX participants_player_ref_idx;
+ALTER TABLE participants DROP COLUMN player_ref;
+ALTER TABLE participants DROP COLUMN puuid;
+DROP TABLE player_names;
+DROP TABLE players;
+DROP TABLE team_names;
+DROP TABLE teams;
@@ -0,0 +1,89 @@
+-- Teams keyed by their GRID id; series.team1_id/team2_id reference teams.grid_id.
+CREATE TABLE teams (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    grid_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    logo_url TEXT
+);
+CREATE UNIQUE INDEX teams_grid_id_key ON teams (grid_id);
+
+CREATE TABLE team_names (
+    team_id INTEGER NOT NULL REFERENCES teams (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    first_seen TEXT,
+    last_seen TEXT,
+    PRIMARY KEY (team_id, name)
+);
+
+-- Players keyed by their Riot puuid. A merged player points at the identity it was merged into.
+CREATE TABLE players (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    puuid TEXT,
+    name TEXT NOT NULL,
+    merged_into INTEGER REFERENCES players (id)
+);
+CREATE UNIQUE INDEX players_puuid_key ON players (puuid);
+
+CREATE TABLE player_names (
+    player_id INTEGER NOT NULL REFERENCES players (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    first_seen TEXT,
+    last_seen TEXT,
+    PRIMARY KEY (player_id, name)
+);
+
+ALTER TABLE participants ADD COLUMN puuid TEXT;
+ALTER TABLE participants ADD COLUMN player_ref INTEGER; -- players.id
+CREATE INDEX participants_player_ref_idx ON participants (player_ref);
+
+-- Backfill teams, newest name first so it becomes the current one.
+INSERT OR IGNORE INTO teams (grid_id, name, logo_url)
+SELECT grid_id, name, logo_url FROM (
+    SELECT team1_id AS grid_id, team1_name AS name, team1_logo AS logo_url, start_time_scheduled
+    FROM series WHERE team1_id IS NOT NULL AND team1_name IS NOT NULL
+    UNION ALL
+    SELECT team2_id, team2_name, team2_logo, start_time_scheduled
+    FROM series WHERE team2_id IS NOT NULL AND team2_name IS NOT NULL
+)
+ORDER BY start_time_scheduled DESC;
+
+INSERT OR IGNORE INTO team_names (team_id, name, first_seen, last_seen)
+SELECT t.id, seen.name, MIN(seen.start_time_scheduled), MAX(seen.start_time_scheduled)
+FROM (
+    SELECT team1_id AS grid_id, team1_name AS name, start_time_scheduled
+    FROM series WHERE team1_name IS NOT NULL
+    UNION ALL
+    SELECT team2_id, team2_name, start_time_scheduled
+    FROM series WHERE team2_name IS NOT NULL
+) AS seen
+JOIN teams t ON t.grid_id = seen.grid_id
+GROUP BY t.id, seen.name;
+
+-- Backfill players from the summary puuid, falling back to one identity per name.
+UPDATE participants SET puuid = json_extract(stats_json, '$.puuid')
+WHERE json_valid(stats_json);
+
+INSERT OR IGNORE INTO players (puuid, name)
+SELECT p.puuid, p.player_name
+FROM participants p
+LEFT JOIN series s ON s.series_id = p.series_id
+WHERE p.puuid IS NOT NULL
+ORDER BY s.start_time_scheduled DESC;
+
+INSERT INTO players (puuid, name)
+SELECT DISTINCT NULL, player_name FROM participants WHERE puuid IS NULL;
+
+UPDATE participants SET player_ref = (
+    SELECT pl.id FROM players pl WHERE pl.puuid = participants.puuid
+) WHERE puuid IS NOT NULL;
+
+UPDATE participants SET player_ref = (
+    SELECT pl.id FROM players pl WHERE pl.puuid IS NULL AND pl.name = participants.player_name
+) WHERE puuid IS NULL;
+
+INSERT OR IGNORE INTO player_names (player_id, name, first_seen, last_seen)
+SELECT p.player_ref, p.player_name, MIN(s.start_time_scheduled), MAX(s.start_time_scheduled)
+FROM participants p
+LEFT JOIN series s ON s.series_id = p.series_id
+WHERE p.player_ref IS NOT NULL
+GROUP BY p.player_ref, p.player_name;
@@ -0,0 +1,11 @@
+DROP TRIGGER participants_search_delete;
+DROP TRIGGER participants_search_update;
+DROP TRIGGER participants_search_insert;
+DROP TRIGGER team_names_search_delete;
+DROP TRIGGER team_names_search_update;
+DROP TRIGGER team_names_search_insert;
+DROP TRIGGER player_names_search_delete;
+DROP TRIGGER player_names_search_update;
+DROP TRIGGER player_names_search_insert;
+DROP INDEX participants_champion_name_idx;
+DROP TABLE search_index;
@@ -0,0 +1,58 @@
+-- One row per searchable name. Players and teams get a row per alias; champions one row each.
+-- remove_diacritics lets "Faker" match "Fåker"; queries use prefix matching for partial tokens.
+CREATE VIRTUAL TABLE search_index USING fts5(
+    kind UNINDEXED,   -- 'player', 'team' or 'champion'
+    ref_id UNINDEXED, -- players.id / teams.id, NULL for champions
+    name,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE INDEX participants_champion_name_idx ON participants (champion_name);
+
+INSERT INTO search_index (kind, ref_id, name)
+SELECT 'player', player_id, name FROM player_names;
+INSERT INTO search_index (kind, ref_id, name)
+SELECT 'team', team_id, name FROM team_names;
+INSERT INTO search_index (kind, ref_id, name)
+SELECT DISTINCT 'champion', NULL, champion_name FROM participants WHERE champion_name != '';
+
+CREATE TRIGGER player_names_search_insert AFTER INSERT ON player_names BEGIN
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('player', NEW.player_id, NEW.name);
+END;
+CREATE TRIGGER player_names_search_update AFTER UPDATE OF player_id, name ON player_names BEGIN
+    DELETE FROM search_index WHERE kind = 'player' AND ref_id = OLD.player_id AND name = OLD.name;
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('player', NEW.player_id, NEW.name);
+END;
+CREATE TRIGGER player_names_search_delete AFTER DELETE ON player_names BEGIN
+    DELETE FROM search_index WHERE kind = 'player' AND ref_id = OLD.player_id AND name = OLD.name;
+END;
+
+CREATE TRIGGER team_names_search_insert AFTER INSERT ON team_names BEGIN
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('team', NEW.team_id, NEW.name);
+END;
+CREATE TRIGGER team_names_search_update AFTER UPDATE OF team_id, name ON team_names BEGIN
+    DELETE FROM search_index WHERE kind = 'team' AND ref_id = OLD.team_id AND name = OLD.name;
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('team', NEW.team_id, NEW.name);
+END;
+CREATE TRIGGER team_names_search_delete AFTER DELETE ON team_names BEGIN
+    DELETE FROM search_index WHERE kind = 'team' AND ref_id = OLD.team_id AND name = OLD.name;
+END;
+
+-- Champions stay indexed while any participant played them.
+CREATE TRIGGER participants_search_insert AFTER INSERT ON participants
+WHEN NEW.champion_name != ''
+    AND NOT EXISTS (SELECT 1 FROM search_index WHERE kind = 'champion' AND name = NEW.champion_name)
+BEGIN
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('champion', NULL, NEW.champion_name);
+END;
+CREATE TRIGGER participants_search_update AFTER UPDATE OF champion_name ON participants
+WHEN NEW.champion_name != ''
+    AND NOT EXISTS (SELECT 1 FROM search_index WHERE kind = 'champion' AND name = NEW.champion_name)
+BEGIN
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('champion', NULL, NEW.champion_name);
+END;
+CREATE TRIGGER participants_search_delete AFTER DELETE ON participants
+WHEN NOT EXISTS (SELECT 1 FROM participants WHERE champion_name = OLD.champion_name)
+BEGIN
+    DELETE FROM search_index WHERE kind = 'champion' AND name = OLD.champion_name;
+END;
@@ -0,0 +1,5 @@
+DROP TRIGGER annotations_search_delete;
+DROP TRIGGER annotations_search_update;
+DROP TRIGGER annotations_search_insert;
+DROP TABLE annotation_tags;
+DROP TABLE annotations;
@@ -0,0 +1,32 @@
+-- Coach notes. Kept out of the synced tables so clearing or resyncing never touches them.
+CREATE TABLE annotations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    series_id TEXT NOT NULL,
+    game INTEGER NOT NULL DEFAULT 0, -- 0 for the whole series
+    note TEXT NOT NULL DEFAULT '',
+    reviewed BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+CREATE UNIQUE INDEX annotations_series_game_key ON annotations (series_id, game);
+
+CREATE TABLE annotation_tags (
+    annotation_id INTEGER NOT NULL REFERENCES annotations (id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (annotation_id, tag)
+);
+CREATE INDEX annotation_tags_tag_idx ON annotation_tags (tag);
+
+CREATE TRIGGER annotations_search_insert AFTER INSERT ON annotations
+WHEN NEW.note != ''
+BEGIN
+    INSERT INTO search_index (kind, ref_id, name) VALUES ('note', NEW.id, NEW.note);
+END;
+CREATE TRIGGER annotations_search_update AFTER UPDATE OF note ON annotations BEGIN
+    DELETE FROM search_index WHERE kind = 'note' AND ref_id = OLD.id;
+    INSERT INTO search_index (kind, ref_id, name)
+    SELECT 'note', NEW.id, NEW.note WHERE NEW.note != '';
+END;
+CREATE TRIGGER annotations_search_delete AFTER DELETE ON annotations BEGIN
+    DELETE FROM search_index WHERE kind = 'note' AND ref_id = OLD.id;
+END;
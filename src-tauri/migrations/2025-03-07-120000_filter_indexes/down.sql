@@ -0,0 +1,5 @@
+DROP INDEX participants_player_name_idx;
+DROP INDEX series_team2_id_idx;
+DROP INDEX series_team1_id_idx;
+DROP INDEX series_patch_idx;
+DROP INDEX series_start_time_scheduled_idx;
@@ -0,0 +1,7 @@
+-- Columns the series filters search on. participants, draft_actions and annotations are
+-- already covered by their (series_id, ...) unique indexes.
+CREATE INDEX series_start_time_scheduled_idx ON series (start_time_scheduled);
+CREATE INDEX series_patch_idx ON series (patch);
+CREATE INDEX series_team1_id_idx ON series (team1_id);
+CREATE INDEX series_team2_id_idx ON series (team2_id);
+CREATE INDEX participants_player_name_idx ON participants (player_name);
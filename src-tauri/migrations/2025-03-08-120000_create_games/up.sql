@@ -0,0 +1,15 @@
+-- Per-game facts from the end-state summary.
+CREATE TABLE games (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    series_id TEXT NOT NULL,
+    game INTEGER NOT NULL, -- 1-based game number within the series
+    duration_seconds INTEGER
+);
+CREATE UNIQUE INDEX games_series_game_key ON games (series_id, game);
+
+-- Only participant stats are stored, not the summary, so the length is the longest
+-- timePlayed. Sync stores the same value (game_duration_seconds), so old and new rows agree.
+INSERT INTO games (series_id, game, duration_seconds)
+SELECT series_id, game, MAX(json_extract(stats_json, '$.timePlayed'))
+FROM participants
+GROUP BY series_id, game;
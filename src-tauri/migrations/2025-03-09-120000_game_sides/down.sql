@@ -0,0 +1,4 @@
+DROP INDEX games_red_team_id_idx;
+DROP INDEX games_blue_team_id_idx;
+ALTER TABLE games DROP COLUMN red_team_id;
+ALTER TABLE games DROP COLUMN blue_team_id;
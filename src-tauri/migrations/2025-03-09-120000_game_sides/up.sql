@@ -0,0 +1,57 @@
+-- GRID team on each side of a game. Draft team names are matched against the series'
+-- team names first, then against every name the two teams have played under.
+ALTER TABLE games ADD COLUMN blue_team_id TEXT;
+ALTER TABLE games ADD COLUMN red_team_id TEXT;
+
+-- Draft sides were guessed from who acted first. Take them from the teamId (100 blue, 200 red)
+-- of the participants playing each team's picks, or the opposite of the other team's, like
+-- sync does (sync::draft::SIDES_FROM_PARTICIPANTS).
+WITH pick_sides AS (
+    SELECT DISTINCT pick.series_id, pick.game, pick.team_name,
+        CASE p.team_id WHEN 100 THEN 'blue' ELSE 'red' END AS side
+    FROM draft_actions pick
+    JOIN participants p ON p.series_id = pick.series_id AND p.game = pick.game
+    WHERE pick.action = 'pick' AND p.team_id IN (100, 200)
+      AND LOWER(REPLACE(REPLACE(REPLACE(p.champion_name, ' ', ''), '''', ''), '.', ''))
+        = LOWER(REPLACE(REPLACE(REPLACE(pick.champion_name, ' ', ''), '''', ''), '.', ''))
+)
+UPDATE draft_actions SET side = COALESCE(
+    (SELECT ps.side FROM pick_sides ps
+     WHERE ps.series_id = draft_actions.series_id AND ps.game = draft_actions.game
+       AND ps.team_name = draft_actions.team_name),
+    (SELECT CASE ps.side WHEN 'blue' THEN 'red' ELSE 'blue' END FROM pick_sides ps
+     WHERE ps.series_id = draft_actions.series_id AND ps.game = draft_actions.game
+       AND ps.team_name <> draft_actions.team_name),
+    side
+);
+
+WITH draft_sides AS (
+    SELECT DISTINCT d.series_id, d.game, d.side,
+        COALESCE(
+            CASE d.team_name WHEN s.team1_name THEN s.team1_id WHEN s.team2_name THEN s.team2_id END,
+            (SELECT t.grid_id FROM teams t JOIN team_names n ON n.team_id = t.id
+             WHERE t.grid_id IN (s.team1_id, s.team2_id) AND n.name = d.team_name)
+        ) AS team_id
+    FROM draft_actions d
+    JOIN series s ON s.series_id = d.series_id
+)
+UPDATE games SET
+    blue_team_id = (SELECT team_id FROM draft_sides ds
+                    WHERE ds.series_id = games.series_id AND ds.game = games.game
+                      AND ds.side = 'blue' AND ds.team_id IS NOT NULL),
+    red_team_id = (SELECT team_id FROM draft_sides ds
+                   WHERE ds.series_id = games.series_id AND ds.game = games.game
+                     AND ds.side = 'red' AND ds.team_id IS NOT NULL);
+
+-- With one side known, the other team took the other side.
+UPDATE games SET red_team_id = (
+    SELECT CASE games.blue_team_id WHEN s.team1_id THEN s.team2_id ELSE s.team1_id END
+    FROM series s WHERE s.series_id = games.series_id
+) WHERE blue_team_id IS NOT NULL AND red_team_id IS NULL;
+UPDATE games SET blue_team_id = (
+    SELECT CASE games.red_team_id WHEN s.team1_id THEN s.team2_id ELSE s.team1_id END
+    FROM series s WHERE s.series_id = games.series_id
+) WHERE red_team_id IS NOT NULL AND blue_team_id IS NULL;
+
+CREATE INDEX games_blue_team_id_idx ON games (blue_team_id);
+CREATE INDEX games_red_team_id_idx ON games (red_team_id);
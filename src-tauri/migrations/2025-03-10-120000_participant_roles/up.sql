@@ -0,0 +1,21 @@
+-- Role each participant played: teamPosition, else Riot's individualPosition guess,
+-- else the participant's place in its team (summaries list each team top to support).
+ALTER TABLE participants ADD COLUMN role TEXT;
+
+UPDATE participants SET role = CASE
+    WHEN team_position IN ('TOP', 'JUNGLE', 'MIDDLE', 'BOTTOM', 'UTILITY') THEN team_position
+    WHEN json_extract(stats_json, '$.individualPosition') IN ('TOP', 'JUNGLE', 'MIDDLE', 'BOTTOM', 'UTILITY')
+        THEN json_extract(stats_json, '$.individualPosition')
+END;
+
+UPDATE participants SET role = (
+    SELECT CASE COUNT(*)
+        WHEN 0 THEN 'TOP' WHEN 1 THEN 'JUNGLE' WHEN 2 THEN 'MIDDLE'
+        WHEN 3 THEN 'BOTTOM' WHEN 4 THEN 'UTILITY'
+    END
+    FROM participants earlier
+    WHERE earlier.series_id = participants.series_id
+      AND earlier.game = participants.game
+      AND earlier.team_id IS participants.team_id
+      AND earlier.id < participants.id
+) WHERE role IS NULL;
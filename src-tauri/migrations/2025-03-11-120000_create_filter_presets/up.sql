@@ -0,0 +1,9 @@
+-- Named sidebar filters. User data, like annotations, so purges and clears leave them alone.
+CREATE TABLE filter_presets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL COLLATE NOCASE,
+    filters TEXT NOT NULL, -- FilterConfig as JSON
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+CREATE UNIQUE INDEX filter_presets_name_key ON filter_presets (name);
@@ -0,0 +1,3 @@
+DROP INDEX series_patch_version_idx;
+ALTER TABLE series DROP COLUMN patch_minor;
+ALTER TABLE series DROP COLUMN patch_major;
@@ -0,0 +1,11 @@
+-- Numeric patch of each series, so patches compare as versions (14.9 < 14.10). NULL while
+-- the patch is unknown ("None" before the summary is fetched, "latest" without gameVersion).
+ALTER TABLE series ADD COLUMN patch_major INTEGER;
+ALTER TABLE series ADD COLUMN patch_minor INTEGER;
+
+UPDATE series SET
+    patch_major = CAST(patch AS INTEGER),
+    patch_minor = CAST(substr(patch, instr(patch, '.') + 1) AS INTEGER)
+WHERE patch GLOB '[0-9]*.[0-9]*';
+
+CREATE INDEX series_patch_version_idx ON series (patch_major, patch_minor);
@@ -0,0 +1,2 @@
+-- The original offsets aren't kept, and UTC timestamps remain valid, so nothing to undo.
+SELECT 1;
@@ -0,0 +1,13 @@
+-- Start times arrive with whatever offset and precision GRID used, which breaks text
+-- comparisons. Store them as UTC to the second, e.g. 2024-05-01T21:30:00Z.
+UPDATE series
+SET start_time_scheduled = strftime('%Y-%m-%dT%H:%M:%SZ', start_time_scheduled)
+WHERE strftime('%Y-%m-%dT%H:%M:%SZ', start_time_scheduled) IS NOT NULL;
+
+UPDATE team_names
+SET first_seen = COALESCE(strftime('%Y-%m-%dT%H:%M:%SZ', first_seen), first_seen),
+    last_seen = COALESCE(strftime('%Y-%m-%dT%H:%M:%SZ', last_seen), last_seen);
+
+UPDATE player_names
+SET first_seen = COALESCE(strftime('%Y-%m-%dT%H:%M:%SZ', first_seen), first_seen),
+    last_seen = COALESCE(strftime('%Y-%m-%dT%H:%M:%SZ', last_seen), last_seen);
@@ -0,0 +1,24 @@
+-- 'blue' or 'red', from the game summary; NULL for remakes and games not yet synced.
+ALTER TABLE games ADD COLUMN winning_side TEXT;
+
+UPDATE games SET winning_side = (
+    SELECT CASE p.team_id WHEN 100 THEN 'blue' WHEN 200 THEN 'red' END
+    FROM participants p
+    WHERE p.series_id = games.series_id AND p.game = games.game AND p.win = 1
+    LIMIT 1
+);
+
+-- Series scores count the games each team won, once every game played is stored with a
+-- known winner. Older syncs stored only the first game, so most keep their scores.
+UPDATE series SET
+    team1_score = (SELECT COUNT(*) FROM games g WHERE g.series_id = series.series_id
+                   AND CASE g.winning_side WHEN 'blue' THEN g.blue_team_id
+                                           WHEN 'red' THEN g.red_team_id END = series.team1_id),
+    team2_score = (SELECT COUNT(*) FROM games g WHERE g.series_id = series.series_id
+                   AND CASE g.winning_side WHEN 'blue' THEN g.blue_team_id
+                                           WHEN 'red' THEN g.red_team_id END = series.team2_id)
+WHERE (SELECT COUNT(*) FROM games g WHERE g.series_id = series.series_id)
+          >= MAX(COALESCE(team1_score, 0) + COALESCE(team2_score, 0), 1)
+  AND NOT EXISTS (SELECT 1 FROM games g WHERE g.series_id = series.series_id
+                  AND CASE g.winning_side WHEN 'blue' THEN g.blue_team_id
+                                          WHEN 'red' THEN g.red_team_id END IS NULL);
@@ -1,26 +1,53 @@
 // src/commands.rs
-use crate::db;
-use crate::db::models::{Participant, Series, TeamInfoStruct};
-use crate::db::schema::participants::dsl::{participants, series_id as participant_series_id};
-use crate::db::schema::participants::player_name;
-use crate::db::schema::series::dsl::series;
+use crate::db::annotations::{self, AnnotationInput};
+use crate::db::models::{
+    DraftAction, Participant, PlayerIdentity, Series, TaggedAnnotation, TeamInfoStruct,
+};
+use crate::db::presets::{self, FilterPreset};
+use crate::db::purge::{self, PurgeReport, PurgeScope};
+use crate::db::schema::participants::dsl::{
+    id as participant_id, participants, series_id as participant_series_id,
+};
+use crate::db::schema::series::dsl::{series, series_id as series_series_id};
+use crate::db::search::SearchResult;
+use crate::db::{self, backup, Database};
+use crate::filters::page::{self, SortKey, SortOrder};
+use crate::filters::{self, query, FilterDiagnostics, ResolvedFilters, SqlFilter};
+use crate::patch::Patch;
+use crate::settings::{self, MyTeam, Settings};
+use crate::sync::entities;
+use crate::sync::schedule::SyncSchedule;
 use crate::sync::sync_once;
+use crate::timestamp::{DateBound, Timezone};
+use chrono::{Local, Weekday};
 use diesel::prelude::*;
 use log::{error, info, warn};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tauri::command;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tauri::{command, State};
 use tokio::time::sleep;
 
+const SEARCH_LIMIT: usize = 50;
+
 // ==============================
 // Filter Configuration Types
 // ==============================
 
+// Inclusive. Each end is a day in the filter's timezone, e.g. "2024-01-01", or an exact
+// instant such as "2024-01-01T00:00:00.000Z".
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DateRange {
-    pub from: Option<String>, // e.g., "2024-01-01T00:00:00.000Z"
-    pub to: Option<String>,
+    pub from: Option<DateBound>,
+    pub to: Option<DateBound>,
+}
+
+// Inclusive; either end may be open.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PatchRange {
+    pub from: Option<Patch>,
+    pub to: Option<Patch>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,6 +55,72 @@ pub struct ChampionSelection {
     pub value: String,
     pub label: String,
     pub champ: String,
+    #[serde(default)]
+    pub role: Option<Role>, // Only when played in this role
+    #[serde(default)]
+    pub team: Option<TeamRelation>, // Only when picked (or banned) by my team or by the opponent
+    #[serde(default)]
+    pub phase: Option<i32>, // Bans only: 1 or 2
+}
+
+// Riot's teamPosition values, accepting the names players use for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    #[serde(rename = "TOP", alias = "top")]
+    Top,
+    #[serde(rename = "JUNGLE", alias = "jungle")]
+    Jungle,
+    #[serde(rename = "MIDDLE", alias = "middle", alias = "mid")]
+    Middle,
+    #[serde(rename = "BOTTOM", alias = "bottom", alias = "bot", alias = "adc")]
+    Bottom,
+    #[serde(rename = "UTILITY", alias = "utility", alias = "support")]
+    Utility,
+}
+
+impl Role {
+    // In the order summaries list each team's participants.
+    pub const ALL: [Role; 5] = [
+        Role::Top,
+        Role::Jungle,
+        Role::Middle,
+        Role::Bottom,
+        Role::Utility,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Top => "TOP",
+            Role::Jungle => "JUNGLE",
+            Role::Middle => "MIDDLE",
+            Role::Bottom => "BOTTOM",
+            Role::Utility => "UTILITY",
+        }
+    }
+
+    fn parse(position: Option<&str>) -> Option<Role> {
+        Role::ALL
+            .into_iter()
+            .find(|role| Some(role.as_str()) == position)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamRelation {
+    Us,
+    Opponent,
+}
+
+// Our champion against theirs in the same role, e.g. our MIDDLE vs Sylas.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Matchup {
+    #[serde(default)]
+    pub ours: Option<String>, // champion name; None for any
+    #[serde(default)]
+    pub theirs: Option<String>,
+    #[serde(default)]
+    pub role: Option<Role>, // None for any lane
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -44,8 +137,25 @@ pub struct PlayerFilter {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Modes {
-    Any,
-    Only,
+    Any,  // at least one of the selection
+    Only, // all of the selection
+    None, // none of the selection
+}
+
+// What wins, losses and draws are counted over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultScope {
+    #[default]
+    Series, // by games won in the series
+    Game, // series with at least one game of that result
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Blue,
+    Red,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,7 +164,17 @@ pub struct FilterConfig {
     pub date_range: DateRange,
     pub wins: bool,
     pub losses: bool,
+    #[serde(default = "shown")]
+    pub draws: bool, // Series with as many games won as lost; games can't be drawn
+    #[serde(default, rename = "resultScope")]
+    pub result_scope: ResultScope,
     pub patch: String,
+    #[serde(default)]
+    pub patches: Vec<Patch>, // Any of these patches
+    #[serde(default, rename = "patchRange")]
+    pub patch_range: PatchRange,
+    #[serde(default, rename = "lastPatches")]
+    pub last_patches: Option<u16>, // The N most recent patches among synced series
     #[serde(rename = "championsPicked")]
     pub champions_picked: Vec<ChampionSelection>,
     #[serde(rename = "champPickedMode")]
@@ -65,6 +185,47 @@ pub struct FilterConfig {
     pub champ_banned_mode: Modes,
     pub teams: Vec<TeamFilter>,
     pub players: Vec<PlayerFilter>,
+    #[serde(default)]
+    pub tags: Vec<String>, // Series with an annotation carrying any of these tags
+    #[serde(default)]
+    pub reviewed: Option<bool>, // Series-level review status; None shows both
+    #[serde(default)]
+    pub side: Option<Side>, // Series where my team (or a selected team) played a game on this side
+    #[serde(default)]
+    pub matchups: Vec<Matchup>, // Series where every lane matchup happened in some game
+    #[serde(default)]
+    pub weekdays: Vec<Weekday>, // Series played on any of these days, e.g. "Tue"
+    #[serde(default)]
+    pub timezone: Timezone, // What days and dates mean; defaults to this computer's timezone
+}
+
+// Older configs without a draws flag kept draws, which counted as wins.
+fn shown() -> bool {
+    true
+}
+
+impl FilterConfig {
+    // Whether any filter depends on knowing my team.
+    pub fn needs_my_team(&self) -> bool {
+        self.filters_by_result()
+            || self.side.is_some()
+            || !self.matchups.is_empty()
+            || self
+                .champions_picked
+                .iter()
+                .chain(&self.champions_banned)
+                .any(|selection| selection.team.is_some())
+    }
+
+    // Whether the result filter hides some outcomes. Showing every outcome only asks for a
+    // known result, and showing none turns the filter off; neither needs my team.
+    pub fn filters_by_result(&self) -> bool {
+        let shown = match self.result_scope {
+            ResultScope::Series => vec![self.wins, self.losses, self.draws],
+            ResultScope::Game => vec![self.wins, self.losses],
+        };
+        shown.contains(&true) && shown.contains(&false)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -167,50 +328,102 @@ pub struct GameTeam {
     pub players: Vec<Player>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Optional throughout so a partial perks entry can't fail the whole participant.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PerkSelection {
+    pub perk: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PerkStyle {
+    pub style: Option<i32>,
+    pub selections: Vec<PerkSelection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Perks {
+    #[serde(default)]
+    pub styles: Vec<PerkStyle>,
+}
+
+// The subset of a summary participant that is promoted to typed columns.
+// Everything else stays available through `participants.stats_json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct GameStats {
-    // pub damageDealtToObjectives: i64,
-    // pub damageDealtToTurrets: i64,
-    // pub detectorWardsPlaced: i64,
-    // pub largestKillingSpree: i64,
-    // pub largestMultiKill: i64,
-    // pub magicDamageDealt: i64,
-    // pub magicDamageDealtToChampions: i64,
-    // pub magicDamageTaken: i64,
-    // pub physicalDamageDealt: i64,
-    // pub physicalDamageDealtToChampions: i64,
-    // pub physicalDamageTaken: i64,
-    // pub totalAllyJungleMinionsKilled: i64,
-    // pub totalDamageDealt: i64,
-    // pub totalDamageDealtToChampions: i64,
-    // pub totalDamageShieldedOnTeammates: i64,
-    // pub totalDamageTaken: i64,
-    // pub totalEnemyJungleMinionsKilled: i64,
-    // pub totalHeal: i64,
-    // pub turretKills: i64,
-    // pub turretTakedowns: i64,
-    // pub visionScore: i64,
-    // pub visionWardsBoughtInGame: i64,
-    // pub wardsKilled: i64,
-    // pub wardsPlaced: i64,
-    // pub perks: Perks, Removed because unused.
-    // pub champLevel: i64,
-    // pub riotIdGameName: String,
-    // pub kills: i64,
-    // pub deaths: i64,
-    // pub assists: i64,
+    pub puuid: Option<String>,
+    #[serde(rename = "riotIdGameName")]
+    pub riot_id_game_name: String,
     #[serde(rename = "championName")]
     pub champion_name: String,
-    // pub item1: i64,
-    // pub item2: i64,
-    // pub item3: i64,
-    // pub item4: i64,
-    // pub item5: i64,
-    // pub item6: i64,
-    // pub item0: i64,
-    // pub totalMinionsKilled: i64,
-    // pub goldEarned: i64,
+    pub kills: Option<i32>,
+    pub deaths: Option<i32>,
+    pub assists: Option<i32>,
+    #[serde(rename = "totalMinionsKilled")]
+    pub total_minions_killed: Option<i32>,
+    #[serde(rename = "neutralMinionsKilled")]
+    pub neutral_minions_killed: Option<i32>,
+    #[serde(rename = "goldEarned")]
+    pub gold_earned: Option<i32>,
+    #[serde(rename = "totalDamageDealtToChampions")]
+    pub total_damage_dealt_to_champions: Option<i32>,
+    #[serde(rename = "totalDamageTaken")]
+    pub total_damage_taken: Option<i32>,
+    #[serde(rename = "visionScore")]
+    pub vision_score: Option<i32>,
+    #[serde(rename = "wardsPlaced")]
+    pub wards_placed: Option<i32>,
+    #[serde(rename = "wardsKilled")]
+    pub wards_killed: Option<i32>,
+    #[serde(rename = "detectorWardsPlaced")]
+    pub detector_wards_placed: Option<i32>,
+    pub item0: Option<i32>,
+    pub item1: Option<i32>,
+    pub item2: Option<i32>,
+    pub item3: Option<i32>,
+    pub item4: Option<i32>,
+    pub item5: Option<i32>,
+    pub item6: Option<i32>,
+    pub perks: Perks,
+    #[serde(rename = "teamPosition")]
+    pub team_position: Option<String>,
+    #[serde(rename = "individualPosition")]
+    pub individual_position: Option<String>,
+    #[serde(rename = "teamId")]
+    pub team_id: Option<i32>,
+    pub win: Option<bool>,
+    #[serde(rename = "timePlayed")]
+    pub time_played: Option<i32>,
 }
+
+impl GameStats {
+    pub fn primary_rune_style(&self) -> Option<i32> {
+        self.perks.styles.first().and_then(|style| style.style)
+    }
+
+    pub fn keystone(&self) -> Option<i32> {
+        self.perks
+            .styles
+            .first()
+            .and_then(|style| style.selections.first())
+            .and_then(|selection| selection.perk)
+    }
+
+    pub fn secondary_rune_style(&self) -> Option<i32> {
+        self.perks.styles.get(1).and_then(|style| style.style)
+    }
+
+    /// teamPosition, else Riot's individualPosition guess, else the participant's place
+    /// within its team, since summaries list each team from top to support.
+    pub fn role(&self, index_in_team: usize) -> Option<Role> {
+        Role::parse(self.team_position.as_deref())
+            .or_else(|| Role::parse(self.individual_position.as_deref()))
+            .or_else(|| Role::ALL.get(index_in_team).copied())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MyOrg {
     pub id: String,
@@ -223,7 +436,7 @@ pub struct TeamNode {
     pub node: TeamInfoShort,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamInfoShort {
     pub id: String,
     pub name: String,
@@ -231,6 +444,13 @@ pub struct TeamInfoShort {
     pub logo_url: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MyTeamCandidates {
+    pub org_name: String,
+    pub candidates: Vec<TeamInfoShort>, // GRID teams whose name contains the organisation's
+    pub selected: Vec<String>,          // team ids currently saved as mine
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TeamsFilterResponse {
     pub data: TeamsFilterData,
@@ -267,10 +487,12 @@ pub struct FilteredSeriesDetail {
     pub patch: String,
 }
 
-pub async fn get_my_team_id(auth_token: String) -> Result<String, String> {
+// My organisation and the GRID teams whose name contains it.
+async fn fetch_team_candidates(auth_token: &str) -> Result<MyTeamCandidates, String> {
+    let settings = settings::load_or_default();
     let client = Client::new();
     let org_response = client
-        .get("https://lol.grid.gg/api/organisations/mine")
+        .get(&settings.api.organisation_url)
         .header("Authorization", format!("Bearer {}", auth_token))
         .send()
         .await
@@ -293,7 +515,7 @@ pub async fn get_my_team_id(auth_token: String) -> Result<String, String> {
 
     // Fetch team ID by name
     let team_id_response = client
-        .post("https://api.grid.gg/central-data/graphql")
+        .post(&settings.api.central_data_url)
         .header("Authorization", format!("Bearer {}", auth_token))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -337,437 +559,877 @@ pub async fn get_my_team_id(auth_token: String) -> Result<String, String> {
             return Err(format!("Failed to parse team ID response: {}", err));
         }
     };
-    let teams: Vec<TeamInfoShort> = team_id_response_json
-        .data
-        .teams
-        .edges
-        .into_iter()
-        .map(|edge| edge.node)
+    Ok(MyTeamCandidates {
+        org_name: my_team_name,
+        candidates: team_id_response_json
+            .data
+            .teams
+            .edges
+            .into_iter()
+            .map(|edge| edge.node)
+            .collect(),
+        selected: settings.my_team.team_ids,
+    })
+}
+
+/// My GRID team ids: the ones saved in settings, else resolved from my organisation.
+/// A single team named exactly like the organisation (or the only match) is saved so
+/// later lookups work offline; several candidates have to be picked in settings.
+pub async fn get_my_team_ids(auth_token: &str) -> Result<Vec<String>, String> {
+    let saved = settings::load_or_default().my_team.team_ids;
+    if !saved.is_empty() {
+        return Ok(saved);
+    }
+
+    let found = fetch_team_candidates(auth_token).await?;
+    let exact: Vec<&TeamInfoShort> = found
+        .candidates
+        .iter()
+        .filter(|team| team.name.trim().eq_ignore_ascii_case(found.org_name.trim()))
         .collect();
-    if teams.is_empty() {
-        warn!("No teams found matching the name '{}'", my_team_name);
-        return Err(format!(
-            "No teams found matching the name '{}'",
-            my_team_name
-        ));
+    let team = match (exact.as_slice(), found.candidates.as_slice()) {
+        ([team], _) => *team,
+        ([], [team]) => team,
+        (_, []) => {
+            warn!("No teams found matching the name '{}'", found.org_name);
+            return Err(format!(
+                "No teams found matching the name '{}'",
+                found.org_name
+            ));
+        }
+        _ => {
+            return Err(format!(
+                "{} teams match the name '{}'. Choose yours under My Team in the filters.",
+                found.candidates.len(),
+                found.org_name
+            ))
+        }
+    };
+    info!("Found team ID: {}", team.id);
+    let team_ids = vec![team.id.clone()];
+
+    // Settings may have changed during the lookup, so only my team is written over a fresh
+    // copy, and a team chosen meanwhile wins. Not saving (e.g. a broken file) isn't fatal.
+    match settings::load() {
+        Ok(settings) if !settings.my_team.team_ids.is_empty() => {
+            return Ok(settings.my_team.team_ids)
+        }
+        Ok(mut settings) => {
+            settings.my_team = MyTeam {
+                team_ids: team_ids.clone(),
+                org_name: Some(found.org_name),
+            };
+            if let Err(err) = settings::save(&settings) {
+                warn!("Failed to save my team: {}", err);
+            }
+        }
+        Err(err) => warn!("Not saving my team: {}", err),
     }
-    let team_id = teams[0].id.clone();
-    info!("Found team ID: {}", team_id);
-    Ok(team_id.to_string())
+    Ok(team_ids)
+}
+
+/// Teams that could be mine, for the user to choose from when the lookup is ambiguous.
+#[command]
+pub async fn get_my_team_candidates(auth_token: String) -> Result<MyTeamCandidates, String> {
+    fetch_team_candidates(&auth_token).await
+}
+
+/// Saves which GRID teams are mine. An empty list resolves them again on next use.
+#[command]
+pub async fn set_my_team(
+    team_ids: Vec<String>,
+    org_name: Option<String>,
+) -> Result<Settings, String> {
+    let mut current = settings::load()?;
+    let mut team_ids: Vec<String> = team_ids
+        .into_iter()
+        .map(|team_id| team_id.trim().to_string())
+        .filter(|team_id| !team_id.is_empty())
+        .collect();
+    team_ids.sort();
+    team_ids.dedup();
+    current.my_team = MyTeam { team_ids, org_name };
+    settings::save(&current)?;
+    Ok(current)
 }
 
 #[command]
-pub async fn get_games() -> Result<Vec<Series>, String> {
-    let mut connection = db::establish_db_connection();
+pub async fn get_games(database: State<'_, Database>) -> Result<Vec<Series>, String> {
+    let mut connection = database.get()?;
     series
         .load::<Series>(&mut connection)
         .map_err(|err| format!("Error querying database: {}", err))
 }
 
 #[command]
-pub async fn get_players(search: String) -> Result<Vec<String>, String> {
-    let mut connection = db::establish_db_connection();
-    let search_param = format!("%{}%", search);
+pub async fn get_players(
+    database: State<'_, Database>,
+    search: String,
+) -> Result<Vec<String>, String> {
+    use crate::db::schema::players::dsl as pl;
+
+    let mut connection = database.get()?;
+    if search.trim().is_empty() {
+        return pl::players
+            .select(pl::name)
+            .distinct()
+            .filter(pl::merged_into.is_null())
+            .load::<String>(&mut connection)
+            .map_err(|err| format!("Error querying database: {}", err));
+    }
 
-    participants
-        .select(player_name)
-        .distinct()
-        .filter(player_name.like(search_param))
-        .load::<String>(&mut connection)
+    // Matches current names and past aliases; merged players resolve to the kept identity.
+    db::search::search(&mut connection, &search, Some("player"), SEARCH_LIMIT)
+        .map(|results| results.into_iter().map(|result| result.name).collect())
         .map_err(|err| format!("Error querying database: {}", err))
 }
 
 #[command]
-pub async fn get_teams(search: String) -> Result<Vec<TeamInfoStruct>, String> {
-    let mut connection = db::establish_db_connection();
+pub async fn get_teams(
+    database: State<'_, Database>,
+    search: String,
+) -> Result<Vec<TeamInfoStruct>, String> {
+    use crate::db::schema::teams::dsl as t;
+
+    let mut connection = database.get()?;
+    let team_ids: Option<Vec<i32>> = if search.trim().is_empty() {
+        None
+    } else {
+        let results = db::search::search(&mut connection, &search, Some("team"), SEARCH_LIMIT)
+            .map_err(|err| format!("Error querying database: {}", err))?;
+        Some(results.into_iter().filter_map(|result| result.id).collect())
+    };
+
+    let mut query = t::teams.select((t::id, t::name, t::logo_url)).into_boxed();
+    if let Some(ref team_ids) = team_ids {
+        query = query.filter(t::id.eq_any(team_ids));
+    }
+    let mut teams = query
+        .load::<(i32, String, Option<String>)>(&mut connection)
+        .map_err(|err| format!("Error querying database: {}", err))?;
+    // Keep the search ranking.
+    if let Some(ref team_ids) = team_ids {
+        teams.sort_by_key(|(id, _, _)| team_ids.iter().position(|ranked| ranked == id));
+    }
+    Ok(teams
+        .into_iter()
+        .map(|(_, team_name, team_logo)| TeamInfoStruct {
+            team_name,
+            team_logo,
+        })
+        .collect())
+}
+
+#[command]
+pub async fn search(
+    database: State<'_, Database>,
+    query: String,
+) -> Result<Vec<SearchResult>, String> {
+    let mut connection = database.get()?;
+    db::search::search(&mut connection, &query, None, SEARCH_LIMIT)
+        .map_err(|err| format!("Error searching: {}", err))
+}
+
+fn load_player_identity(
+    connection: &mut SqliteConnection,
+    player_id: i32,
+) -> QueryResult<PlayerIdentity> {
+    use crate::db::schema::player_names::dsl as pn;
+    use crate::db::schema::players::dsl as pl;
+
+    let player = pl::players
+        .find(player_id)
+        .first::<crate::db::models::Player>(connection)?;
+    let aliases = pn::player_names
+        .filter(pn::player_id.eq(player_id))
+        .order(pn::last_seen.desc())
+        .select(pn::name)
+        .load::<String>(connection)?;
+    Ok(PlayerIdentity {
+        id: player.id,
+        puuid: player.puuid,
+        name: player.name,
+        aliases,
+    })
+}
+
+#[command]
+pub async fn get_player_identities(
+    database: State<'_, Database>,
+    search: String,
+) -> Result<Vec<PlayerIdentity>, String> {
+    use crate::db::schema::player_names::dsl as pn;
+    use crate::db::schema::players::dsl as pl;
+
+    let mut connection = database.get()?;
     let search_param = format!("%{}%", search);
-    let query = r#"
-        SELECT team_name, team_logo FROM (
-            SELECT team1_name AS team_name, team1_logo AS team_logo 
-            FROM series 
-            WHERE team1_name IS NOT NULL
-            UNION
-            SELECT team2_name AS team_name, team2_logo AS team_logo 
-            FROM series 
-            WHERE team2_name IS NOT NULL
-        ) AS teams
-        WHERE team_name LIKE ?
-    "#;
-    diesel::sql_query(query)
-        .bind::<diesel::sql_types::Text, _>(search_param)
-        .load::<TeamInfoStruct>(&mut connection)
+    let player_ids = pl::players
+        .select(pl::id)
+        .filter(pl::merged_into.is_null())
+        .filter(
+            pl::name.like(search_param.clone()).or(diesel::dsl::exists(
+                pn::player_names
+                    .filter(pn::player_id.eq(pl::id))
+                    .filter(pn::name.like(search_param)),
+            )),
+        )
+        .order(pl::name.asc())
+        .load::<i32>(&mut connection)
+        .map_err(|err| format!("Error querying database: {}", err))?;
+
+    player_ids
+        .into_iter()
+        .map(|player_id| load_player_identity(&mut connection, player_id))
+        .collect::<QueryResult<Vec<_>>>()
         .map_err(|err| format!("Error querying database: {}", err))
 }
 
+/// Folds `merge_id` into `keep_id`: its games, aliases and future syncs of its puuid all
+/// count towards `keep_id` from now on.
 #[command]
-pub async fn start_sync(auth_token: String) -> Result<String, String> {
+pub async fn merge_players(
+    database: State<'_, Database>,
+    keep_id: i32,
+    merge_id: i32,
+) -> Result<PlayerIdentity, String> {
+    use crate::db::schema::participants::dsl as p;
+    use crate::db::schema::player_names::dsl as pn;
+    use crate::db::schema::players::dsl as pl;
+    use diesel::result::Error;
+
+    let mut connection = database.get()?;
+    connection
+        .transaction::<_, Error, _>(|conn| {
+            let keep_id = entities::resolve_player(conn, keep_id)?;
+            let merge_id = entities::resolve_player(conn, merge_id)?;
+            if keep_id == merge_id {
+                return load_player_identity(conn, keep_id);
+            }
+
+            let reassigned = diesel::update(p::participants.filter(p::player_ref.eq(merge_id)))
+                .set(p::player_ref.eq(keep_id))
+                .execute(conn)?;
+
+            let aliases = pn::player_names
+                .filter(pn::player_id.eq(merge_id))
+                .select((pn::name, pn::first_seen, pn::last_seen))
+                .load::<(String, Option<String>, Option<String>)>(conn)?;
+            for (alias, first_seen, last_seen) in &aliases {
+                entities::record_name(
+                    conn,
+                    "player_names",
+                    "player_id",
+                    keep_id,
+                    alias,
+                    first_seen.as_deref(),
+                )?;
+                entities::record_name(
+                    conn,
+                    "player_names",
+                    "player_id",
+                    keep_id,
+                    alias,
+                    last_seen.as_deref(),
+                )?;
+            }
+            diesel::delete(pn::player_names.filter(pn::player_id.eq(merge_id))).execute(conn)?;
+
+            diesel::update(
+                pl::players.filter(pl::id.eq(merge_id).or(pl::merged_into.eq(merge_id))),
+            )
+            .set(pl::merged_into.eq(keep_id))
+            .execute(conn)?;
+            entities::refresh_player_name(conn, keep_id)?;
+
+            info!(
+                "Merged player {} into {} ({} participant rows reassigned)",
+                merge_id, keep_id, reassigned
+            );
+            load_player_identity(conn, keep_id)
+        })
+        .map_err(|err| format!("Failed to merge players: {}", err))
+}
+
+#[command]
+pub async fn start_sync(
+    database: State<'_, Database>,
+    auth_token: String,
+) -> Result<String, String> {
     info!("Starting sync process with authentication...");
+    let pool = database.pool()?;
     loop {
-        match sync_once(auth_token.clone()).await {
+        // Re-read the schedule every cycle so changes apply without restarting the sync.
+        let sync_schedule = settings::load_or_default().sync;
+        let now = Local::now().naive_local();
+        if sync_schedule.is_quiet(now) {
+            let wait = sync_schedule.until_quiet_end(now);
+            info!("Quiet hours active, next sync in {:?}", wait);
+            sleep(wait).await;
+            continue;
+        }
+
+        match sync_once(&pool, auth_token.clone()).await {
             Ok(count) => info!("Sync completed: {} series updated", count),
             Err(err) => {
                 error!("Sync error: {}", err);
                 return Err(err.to_string());
             }
         }
-        sleep(Duration::from_secs(600)).await; // Sync every 10 minutes
+        let retention = settings::load_or_default().retention;
+        let retained = db::get_connection(&pool).and_then(|mut connection| {
+            purge::apply_retention(&mut connection, &retention, Local::now().date_naive())
+        });
+        if let Err(err) = retained {
+            error!("Failed to apply retention policy: {}", err);
+        }
+        let wait = sync_schedule.next_delay(Local::now().naive_local());
+        info!("Next sync in {:?}", wait);
+        sleep(wait).await;
     }
 }
 
 #[command]
-pub async fn clear_db() -> Result<String, String> {
-    use crate::db::schema::participants::dsl::participants;
-    use crate::db::schema::series::dsl::series;
-    use diesel::result::Error;
+pub async fn get_sync_schedule() -> Result<SyncSchedule, String> {
+    Ok(settings::load_or_default().sync)
+}
 
-    let mut connection = db::establish_db_connection();
+#[command]
+pub async fn update_sync_schedule(sync_schedule: SyncSchedule) -> Result<SyncSchedule, String> {
+    let mut current = settings::load()?;
+    current.sync = sync_schedule;
+    settings::save(&current)?;
+    Ok(current.sync)
+}
 
-    connection
-        .transaction::<_, Error, _>(|conn| {
-            let deleted_participants =
-                diesel::delete(participants).execute(conn).map_err(|err| {
-                    error!("Error clearing participants table: {}", err);
-                    err
-                })?;
-            let deleted_series = diesel::delete(series).execute(conn).map_err(|err| {
-                error!("Error clearing series table: {}", err);
-                err
-            })?;
+#[command]
+pub async fn get_settings() -> Result<Settings, String> {
+    settings::load()
+}
 
-            info!(
-                "Database cleared: {} participants and {} series deleted.",
-                deleted_participants, deleted_series
-            );
-            Ok((deleted_participants, deleted_series))
-        })
-        .map(|(deleted_participants, deleted_series)| {
-            format!(
-                "Database cleared: {} participants and {} series deleted.",
-                deleted_participants, deleted_series
-            )
-        })
-        .map_err(|err| format!("Failed to clear database: {}", err))
+#[command]
+pub async fn update_settings(new_settings: Settings) -> Result<Settings, String> {
+    settings::save(&new_settings)?;
+    Ok(new_settings)
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct SeriesWithParticipants {
-    pub series: Series,
-    pub participants: Vec<Participant>,
+#[command]
+pub async fn clear_db(database: State<'_, Database>) -> Result<String, String> {
+    let mut connection = database.get()?;
+    backup::snapshot(&mut connection, "pre-clear")?;
+
+    let report = purge::clear_all(&mut connection).inspect_err(|err| error!("{}", err))?;
+    let message = format!(
+        "Database cleared: {} participants, {} event logs, {} draft actions, {} games and {} series deleted.",
+        report.participants, report.event_logs, report.draft_actions, report.games, report.series
+    );
+    info!("{}", message);
+    Ok(message)
 }
+
 #[command]
-pub async fn get_series_with_participants(
-    filters: FilterConfig,
-    auth_token: String,
-) -> Result<Vec<SeriesWithParticipants>, String> {
-    // Helper to check if two patch strings match for the first segments.
-    fn patch_matches(series_patch: &str, filter_patch: &str) -> bool {
-        let normalize = |s: &str| {
-            s.split('.')
-                .map(|part| part.parse::<u32>().unwrap_or(0))
-                .collect::<Vec<u32>>()
-        };
-        let series_parts = normalize(series_patch);
-        let filter_parts = normalize(filter_patch);
-
-        // Determine the number of segments to compare:
-        let segments_to_compare = if series_parts.len() >= 2 && filter_parts.len() >= 2 {
-            2
-        } else {
-            std::cmp::min(series_parts.len(), filter_parts.len())
-        };
+pub async fn purge_series(
+    database: State<'_, Database>,
+    scope: PurgeScope,
+    preset: Option<String>,
+) -> Result<PurgeReport, String> {
+    let mut connection = database.get()?;
+    let series_ids = match preset {
+        Some(ref name) => preset_series(&mut connection, name, &scope)?,
+        None => purge::matching_series(&mut connection, &scope)?,
+    };
+    if series_ids.is_empty() {
+        return Ok(PurgeReport::default());
+    }
+    backup::snapshot(&mut connection, "pre-purge")?;
+    let report = purge::purge_series(&mut connection, &series_ids)?;
+    info!(
+        "Purged {} series matching {:?} and preset {:?}",
+        report.series, scope, preset
+    );
+    Ok(report)
+}
 
-        for i in 0..segments_to_compare {
-            if series_parts[i] != filter_parts[i] {
-                return false;
-            }
-        }
-        true
+// Series matching a preset, narrowed by `scope` when it sets anything. Purging doesn't look
+// my team up, so a preset that filters on it needs my team saved in settings; otherwise its
+// win/side filters would be dropped and purge more than the preset shows.
+fn preset_series(
+    connection: &mut SqliteConnection,
+    name: &str,
+    scope: &PurgeScope,
+) -> Result<Vec<String>, String> {
+    let filters = presets::find_by_name(connection, name)?.filters;
+    let my_team_ids = settings::load_or_default().my_team.team_ids;
+    if filters.needs_my_team() && my_team_ids.is_empty() {
+        return Err(format!(
+            "Preset '{}' filters on my team, which isn't set. Choose it in settings first.",
+            name
+        ));
     }
+    let resolved = ResolvedFilters::resolve(connection, &filters, my_team_ids)
+        .map_err(|err| format!("Error resolving filters: {}", err))?;
+    let mut series_ids =
+        filters::matching_series_ids(connection, &filters::build(&filters, &resolved))
+            .map_err(|err| format!("Error querying series: {}", err))?;
+    if !scope.is_empty() {
+        let in_scope: HashSet<String> = purge::matching_series(connection, scope)?
+            .into_iter()
+            .collect();
+        series_ids.retain(|series_id| in_scope.contains(series_id));
+    }
+    Ok(series_ids)
+}
 
-    // Helper to extract banned champions from an event log string.
-    // This expects the event log to be a JSON array where each element is an object like:
-    // { "node": { "type": "team-banned-character", "sentenceChunks": [ { "text": "Blue" }, { "text": "banned" }, { "text": "ChampionName" } ] } }
-    fn extract_banned_champions(event_log_str: &str) -> Vec<String> {
-        let mut banned = Vec::new();
-        if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(event_log_str) {
-            if let Some(events) = json_value.as_array() {
-                for event in events {
-                    if let Some(node) = event.get("node") {
-                        if let Some(event_type) = node.get("type").and_then(|v| v.as_str()) {
-                            if event_type == "team-banned-character" {
-                                if let Some(chunks) =
-                                    node.get("sentenceChunks").and_then(|v| v.as_array())
-                                {
-                                    // Expect at least three chunks: [team, "banned", champion]
-                                    if chunks.len() >= 3 {
-                                        if let Some(champion) =
-                                            chunks[2].get("text").and_then(|v| v.as_str())
-                                        {
-                                            banned.push(champion.to_string());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        banned
+#[command]
+pub async fn get_annotations(
+    database: State<'_, Database>,
+    series_id: String,
+) -> Result<Vec<TaggedAnnotation>, String> {
+    let mut connection = database.get()?;
+    annotations::load_for_series(&mut connection, std::slice::from_ref(&series_id))
+        .map(|mut by_series| by_series.remove(&series_id).unwrap_or_default())
+        .map_err(|err| format!("Error querying annotations: {}", err))
+}
+
+#[command]
+pub async fn save_annotation(
+    database: State<'_, Database>,
+    annotation: AnnotationInput,
+) -> Result<TaggedAnnotation, String> {
+    let mut connection = database.get()?;
+    annotations::save(&mut connection, &annotation)
+        .map_err(|err| format!("Failed to save annotation: {}", err))
+}
+
+#[command]
+pub async fn delete_annotation(
+    database: State<'_, Database>,
+    annotation_id: i32,
+) -> Result<(), String> {
+    let mut connection = database.get()?;
+    match annotations::delete(&mut connection, annotation_id) {
+        Ok(0) => Err(format!("Annotation {} not found", annotation_id)),
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("Failed to delete annotation: {}", err)),
     }
+}
 
-    let mut connection = db::establish_db_connection();
+#[command]
+pub async fn get_annotation_tags(database: State<'_, Database>) -> Result<Vec<String>, String> {
+    let mut connection = database.get()?;
+    annotations::all_tags(&mut connection)
+        .map_err(|err| format!("Error querying annotation tags: {}", err))
+}
 
-    // Fetch all series from the database.
-    let all_series: Vec<Series> = match series.load::<Series>(&mut connection) {
-        Ok(series_result) => series_result,
-        Err(err) => return Err(format!("Error querying series: {}", err)),
-    };
+#[command]
+pub async fn get_filter_presets(
+    database: State<'_, Database>,
+) -> Result<Vec<FilterPreset>, String> {
+    let mut connection = database.get()?;
+    presets::list(&mut connection)
+}
 
-    // If wins/losses filtering is enabled, try to get the user's team ID.
-    let mut my_team_id: Option<String> = None;
-    if filters.wins || filters.losses {
-        match get_my_team_id(auth_token.clone()).await {
-            Ok(team_id) => {
-                my_team_id = Some(team_id);
-            }
-            Err(err) => {
-                warn!(
-                    "Could not determine my_team_id: {}. Wins/Losses filter will be ignored.",
-                    err
-                );
-            }
-        }
+#[command]
+pub async fn get_filter_preset(
+    database: State<'_, Database>,
+    name: String,
+) -> Result<FilterPreset, String> {
+    let mut connection = database.get()?;
+    presets::find_by_name(&mut connection, &name)
+}
+
+#[command]
+pub async fn create_filter_preset(
+    database: State<'_, Database>,
+    name: String,
+    filters: FilterConfig,
+) -> Result<FilterPreset, String> {
+    let mut connection = database.get()?;
+    presets::create(&mut connection, &name, &filters)
+}
+
+#[command]
+pub async fn update_filter_preset(
+    database: State<'_, Database>,
+    preset_id: i32,
+    name: String,
+    filters: FilterConfig,
+) -> Result<FilterPreset, String> {
+    let mut connection = database.get()?;
+    presets::update(&mut connection, preset_id, &name, &filters)
+}
+
+#[command]
+pub async fn delete_filter_preset(
+    database: State<'_, Database>,
+    preset_id: i32,
+) -> Result<(), String> {
+    let mut connection = database.get()?;
+    match presets::delete(&mut connection, preset_id) {
+        Ok(0) => Err(format!("Preset {} not found", preset_id)),
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("Failed to delete preset: {}", err)),
     }
+}
 
-    let mut results = Vec::new();
+#[command]
+pub async fn backup_db(database: State<'_, Database>, path: String) -> Result<String, String> {
+    let mut connection = database.get()?;
+    backup::backup_to(&mut connection, Path::new(&path))?;
+    info!("Backed up database to {}", path);
+    Ok(format!("Database backed up to {}", path))
+}
 
-    for series_entry in all_series {
-        // ---- Filter by Patch ----
-        if !filters.patch.is_empty() && !patch_matches(&series_entry.patch, &filters.patch) {
-            info!(
-                "Series ID {} excluded due to patch mismatch: {} does not match {}",
-                series_entry.series_id, series_entry.patch, filters.patch
-            );
-            continue;
+#[command]
+pub async fn restore_db(database: State<'_, Database>, path: String) -> Result<String, String> {
+    let mut connection = database.get()?;
+    let restored = backup::restore_from(&mut connection, Path::new(&path))?;
+    Ok(format!("Restored {} rows from {}", restored, path))
+}
+
+#[command]
+pub async fn get_draft(
+    database: State<'_, Database>,
+    series_id: String,
+    game: i32,
+) -> Result<Vec<DraftAction>, String> {
+    use crate::db::schema::draft_actions::dsl as d;
+
+    let mut connection = database.get()?;
+    d::draft_actions
+        .filter(d::series_id.eq(&series_id))
+        .filter(d::game.eq(game))
+        .order(d::sequence.asc())
+        .load::<DraftAction>(&mut connection)
+        .map_err(|err| format!("Error querying database: {}", err))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SeriesWithParticipants {
+    pub series: Series,
+    pub participants: Vec<Participant>,
+    pub games: Vec<crate::db::models::Game>, // synced games, with the team on each side
+    pub annotations: Vec<TaggedAnnotation>,
+}
+// Looks up my team when the filters or sort order depend on it. When it can't be
+// determined, the error comes back with no teams so the caller can report it.
+async fn my_team_for(auth_token: &str, needed: bool) -> (Vec<String>, Option<String>) {
+    if !needed {
+        return (Vec::new(), None);
+    }
+    match get_my_team_ids(auth_token).await {
+        Ok(team_ids) => (team_ids, None),
+        Err(err) => {
+            warn!("Could not determine my team: {}", err);
+            (Vec::new(), Some(err))
         }
+    }
+}
 
-        // ---- Filter by Date Range ----
-        if filters.date_range.from.is_some() || filters.date_range.to.is_some() {
-            if let Some(ref start_time) = series_entry.start_time_scheduled {
-                if let Some(ref from_date) = filters.date_range.from {
-                    if start_time < from_date {
-                        info!(
-                            "Series ID {} excluded because start time {} is before {}",
-                            series_entry.series_id, start_time, from_date
-                        );
-                        continue;
-                    }
-                }
-                if let Some(ref to_date) = filters.date_range.to {
-                    if start_time > to_date {
-                        info!(
-                            "Series ID {} excluded because start time {} is after {}",
-                            series_entry.series_id, start_time, to_date
-                        );
-                        continue;
-                    }
-                }
-            } else {
-                info!(
-                    "Series ID {} excluded because it has no start time for date filtering",
-                    series_entry.series_id
-                );
-                continue;
-            }
+// The filters a command applies: given directly, or the preset with this name. Never both.
+fn filters_or_preset(
+    database: &Database,
+    filters: Option<FilterConfig>,
+    preset: Option<String>,
+) -> Result<Option<FilterConfig>, String> {
+    match (filters, preset) {
+        (Some(_), Some(_)) => Err("Pass either filters or a preset, not both".to_string()),
+        (None, Some(name)) => {
+            let mut connection = database.get()?;
+            Ok(Some(presets::find_by_name(&mut connection, &name)?.filters))
         }
+        (filters, None) => Ok(filters),
+    }
+}
 
-        // ---- Filter by Wins/Losses ----
-        if filters.wins || filters.losses {
-            if series_entry.team1_id.is_none()
-                || series_entry.team2_id.is_none()
-                || series_entry.team1_score.is_none()
-                || series_entry.team2_score.is_none()
-            {
-                info!(
-                    "Series ID {} excluded because team information is missing",
-                    series_entry.series_id
-                );
-                continue;
-            }
-            let team1_id = series_entry.team1_id.clone().unwrap();
-            let team2_id = series_entry.team2_id.clone().unwrap();
-            let team1_score = series_entry.team1_score.unwrap();
-            let team2_score = series_entry.team2_score.unwrap();
-
-            if let Some(ref my_team) = my_team_id {
-                if &team1_id != my_team && &team2_id != my_team {
-                    info!(
-                        "Series ID {} excluded because it doesn't involve my team",
-                        series_entry.series_id
-                    );
-                    continue;
-                }
+fn required_filters(
+    database: &Database,
+    filters: Option<FilterConfig>,
+    preset: Option<String>,
+) -> Result<FilterConfig, String> {
+    filters_or_preset(database, filters, preset)?
+        .ok_or_else(|| "Pass filters or a preset".to_string())
+}
 
-                let my_team_won = (team1_id == *my_team && team1_score >= team2_score)
-                    || (team2_id == *my_team && team2_score >= team1_score);
-                let my_team_lost = (team1_id == *my_team && team1_score < team2_score)
-                    || (team2_id == *my_team && team2_score < team1_score);
+#[derive(Serialize)]
+pub struct SeriesResults {
+    pub series: Vec<SeriesWithParticipants>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<FilterDiagnostics>, // only when no series match or my team is unknown
+}
 
-                let show_wins = filters.wins && my_team_won;
-                let show_losses = filters.losses && my_team_lost;
+#[command]
+pub async fn get_series_with_participants(
+    database: State<'_, Database>,
+    filters: Option<FilterConfig>,
+    preset: Option<String>,
+    auth_token: String,
+) -> Result<SeriesResults, String> {
+    let filters = required_filters(&database, filters, preset)?;
+    let (my_team_ids, my_team_error) = my_team_for(&auth_token, filters.needs_my_team()).await;
+
+    // Only check out a connection once the network lookup is done.
+    let mut connection = database.get()?;
+
+    // Resolve team and player names once so renamed teams and players still match.
+    let resolved = ResolvedFilters::resolve(&mut connection, &filters, my_team_ids)
+        .map_err(|err| format!("Error resolving filters: {}", err))?;
+    let sql_filter = filters::build(&filters, &resolved);
+    let series_ids = filters::matching_series_ids(&mut connection, &sql_filter)
+        .map_err(|err| format!("Error querying series: {}", err))?;
+    info!("{} series match the filters", series_ids.len());
+
+    // Say which filter emptied the list, or that my team is unknown, so the sidebar can show
+    // more than "No Series Found." or a list the team filters didn't apply to.
+    let diagnostics = if series_ids.is_empty() || my_team_error.is_some() {
+        let mut diagnostics = filters::diagnose(&mut connection, &sql_filter)
+            .map_err(|err| format!("Error diagnosing filters: {}", err))?;
+        diagnostics.my_team_error = my_team_error;
+        Some(diagnostics)
+    } else {
+        None
+    };
+    let results = hydrate_series(&mut connection, &series_ids)
+        .map_err(|err| format!("Error loading series: {}", err))?;
+    Ok(SeriesResults {
+        series: results,
+        diagnostics,
+    })
+}
 
-                if !(show_wins || show_losses) {
-                    info!(
-                        "Series ID {} excluded based on win/loss filters",
-                        series_entry.series_id
-                    );
-                    continue;
-                }
-            }
-        }
+#[derive(Serialize)]
+pub struct SeriesListItem {
+    #[serde(flatten)]
+    pub series: Series,
+    pub duration_seconds: Option<i32>, // total across synced games
+    pub games: i64,                    // games with a synced summary
+}
 
-        // ---- Filter by Teams ----
-        if !filters.teams.is_empty() {
-            let allowed_team_names: Vec<String> =
-                filters.teams.iter().map(|t| t.value.clone()).collect();
-            let team1_ok = series_entry
-                .team1_name
-                .as_ref()
-                .map(|name| allowed_team_names.contains(name))
-                .unwrap_or(false);
-            let team2_ok = series_entry
-                .team2_name
-                .as_ref()
-                .map(|name| allowed_team_names.contains(name))
-                .unwrap_or(false);
-            if !team1_ok && !team2_ok {
-                info!(
-                    "Series ID {} excluded due to team filter",
-                    series_entry.series_id
-                );
-                continue;
-            }
+#[derive(Serialize)]
+pub struct SeriesPage {
+    pub items: Vec<SeriesListItem>,
+    pub next_cursor: Option<String>, // pass back as `cursor` for the next page
+    pub total: i64,                  // matching series across all pages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<FilterDiagnostics>, // when requested or my team is unknown
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)] // Tauri commands take their arguments by name
+pub async fn list_series(
+    database: State<'_, Database>,
+    filters: Option<FilterConfig>,
+    preset: Option<String>,
+    auth_token: String,
+    sort: Option<SortOrder>,
+    cursor: Option<String>,
+    page_size: Option<usize>,
+    diagnostics: Option<bool>,
+) -> Result<SeriesPage, String> {
+    let filters = required_filters(&database, filters, preset)?;
+    let sort = sort.unwrap_or_default();
+    let needs_my_team =
+        filters.needs_my_team() || matches!(sort.key, SortKey::Opponent | SortKey::Result);
+    let (my_team_ids, my_team_error) = my_team_for(&auth_token, needs_my_team).await;
+
+    let mut connection = database.get()?;
+    let resolved = ResolvedFilters::resolve(&mut connection, &filters, my_team_ids)
+        .map_err(|err| format!("Error resolving filters: {}", err))?;
+    let sql_filter = filters::build(&filters, &resolved);
+    let page = page::load_page(
+        &mut connection,
+        &sql_filter,
+        sort,
+        &resolved.my_team_ids,
+        cursor.as_deref(),
+        page_size.unwrap_or(page::DEFAULT_PAGE_SIZE),
+    )?;
+
+    let items = list_items(&mut connection, &page.series_ids)
+        .map_err(|err| format!("Error loading series: {}", err))?;
+    let diagnostics = if diagnostics.unwrap_or(false) || my_team_error.is_some() {
+        let mut diagnostics = filters::diagnose(&mut connection, &sql_filter)
+            .map_err(|err| format!("Error diagnosing filters: {}", err))?;
+        diagnostics.my_team_error = my_team_error;
+        Some(diagnostics)
+    } else {
+        None
+    };
+    Ok(SeriesPage {
+        items,
+        next_cursor: page.next_cursor,
+        total: page.total,
+        diagnostics,
+    })
+}
+
+/// Series matching a filter expression such as
+/// `(Azir OR Orianna picked by us) AND NOT lost AND patch >= 14.10`, optionally
+/// narrowed further by the structured sidebar filters. Dates and weekdays are read in
+/// `timezone`, else the filters' timezone, else this computer's.
+#[command]
+pub async fn query_series(
+    database: State<'_, Database>,
+    expr: String,
+    auth_token: String,
+    filters: Option<FilterConfig>,
+    preset: Option<String>,
+    timezone: Option<Timezone>,
+) -> Result<Vec<SeriesWithParticipants>, String> {
+    let expr = query::parse(&expr).map_err(|err| err.to_string())?;
+    let filters = filters_or_preset(&database, filters, preset)?;
+    let needs_my_team =
+        expr.needs_my_team() || filters.as_ref().is_some_and(FilterConfig::needs_my_team);
+    // The query can't be evaluated as written without my team, so the lookup error is the answer.
+    let (my_team_ids, my_team_error) = my_team_for(&auth_token, needs_my_team).await;
+    if let Some(err) = my_team_error {
+        return Err(format!("Could not determine my team: {}", err));
+    }
+
+    let mut connection = database.get()?;
+    let timezone = timezone
+        .or_else(|| filters.as_ref().map(|filters| filters.timezone))
+        .unwrap_or_default();
+    let (condition, params) = expr.compile(&mut connection, &my_team_ids, timezone)?;
+    let mut sql_filter = match filters {
+        Some(ref filters) => {
+            let resolved = ResolvedFilters::resolve(&mut connection, filters, my_team_ids.clone())
+                .map_err(|err| format!("Error resolving filters: {}", err))?;
+            filters::build(filters, &resolved)
         }
+        None => SqlFilter::default(),
+    };
+    sql_filter.push("query", condition, params);
 
-        // ---- Fetch Participants for this Series ----
-        let series_id_val = series_entry.series_id.clone();
-        let all_participants: Vec<Participant> = match participants
-            .filter(participant_series_id.eq(&series_id_val))
-            .load::<Participant>(&mut connection)
-        {
-            Ok(participants_val) => participants_val,
-            Err(err) => {
-                return Err(format!(
-                    "Error querying participants for series {}: {}",
-                    series_id_val, err
-                ));
-            }
+    let series_ids = filters::matching_series_ids(&mut connection, &sql_filter)
+        .map_err(|err| format!("Error querying series: {}", err))?;
+    info!("{} series match the query", series_ids.len());
+    hydrate_series(&mut connection, &series_ids)
+        .map_err(|err| format!("Error loading series: {}", err))
+}
+
+/// How many series each filter stage excludes, to explain an empty or small result.
+#[command]
+pub async fn diagnose_filters(
+    database: State<'_, Database>,
+    filters: Option<FilterConfig>,
+    preset: Option<String>,
+    auth_token: String,
+) -> Result<FilterDiagnostics, String> {
+    let filters = required_filters(&database, filters, preset)?;
+    let (my_team_ids, my_team_error) = my_team_for(&auth_token, filters.needs_my_team()).await;
+
+    let mut connection = database.get()?;
+    let resolved = ResolvedFilters::resolve(&mut connection, &filters, my_team_ids)
+        .map_err(|err| format!("Error resolving filters: {}", err))?;
+    let sql_filter = filters::build(&filters, &resolved);
+    let mut diagnostics = filters::diagnose(&mut connection, &sql_filter)
+        .map_err(|err| format!("Error diagnosing filters: {}", err))?;
+    diagnostics.my_team_error = my_team_error;
+    Ok(diagnostics)
+}
+
+// Series rows plus game totals, without participants, keeping the order of `series_ids`.
+fn list_items(
+    connection: &mut SqliteConnection,
+    series_ids: &[String],
+) -> QueryResult<Vec<SeriesListItem>> {
+    use crate::db::schema::games::dsl as g;
+
+    let mut series_by_id: HashMap<String, Series> = series
+        .filter(series_series_id.eq_any(series_ids))
+        .load::<Series>(connection)?
+        .into_iter()
+        .map(|series_entry| (series_entry.series_id.clone(), series_entry))
+        .collect();
+    let mut totals: HashMap<String, (Option<i32>, i64)> = HashMap::new();
+    for (series_id, duration) in g::games
+        .filter(g::series_id.eq_any(series_ids))
+        .select((g::series_id, g::duration_seconds))
+        .load::<(String, Option<i32>)>(connection)?
+    {
+        let total = totals.entry(series_id).or_insert((None, 0));
+        total.0 = match (total.0, duration) {
+            (Some(sum), Some(duration)) => Some(sum + duration),
+            (sum, duration) => sum.or(duration),
         };
+        total.1 += 1;
+    }
 
-        // ---- Filter by Champions Picked ----
-        if !filters.champions_picked.is_empty() {
-            let picked_champions: Vec<String> = filters
-                .champions_picked
-                .iter()
-                .map(|c| c.champ.clone())
-                .collect();
-            match filters.champ_picked_mode {
-                Modes::Any => {
-                    let picked_in_game = picked_champions
-                        .iter()
-                        .any(|champ| all_participants.iter().any(|p| p.champion_name == *champ));
-                    if !picked_in_game {
-                        info!(
-                            "Series ID {} excluded because no picked champions were found",
-                            series_entry.series_id
-                        );
-                        continue;
-                    }
-                }
-                Modes::Only => {
-                    let picked_in_game = picked_champions
-                        .iter()
-                        .all(|champ| all_participants.iter().any(|p| p.champion_name == *champ));
-                    if !picked_in_game {
-                        info!(
-                            "Series ID {} excluded because not all picked champions were found",
-                            series_entry.series_id
-                        );
-                        continue;
-                    }
-                }
-            }
-        }
+    Ok(series_ids
+        .iter()
+        .filter_map(|series_id| {
+            let (duration, games) = totals.remove(series_id).unwrap_or((None, 0));
+            Some(SeriesListItem {
+                series: series_by_id.remove(series_id)?,
+                duration_seconds: duration,
+                games,
+            })
+        })
+        .collect())
+}
 
-        // ---- Filter by Champions Banned (using event log) ----
-        if !filters.champions_banned.is_empty() {
-            let banned_filter: Vec<String> = filters
-                .champions_banned
-                .iter()
-                .map(|c| c.champ.clone())
-                .collect();
-
-            // Query the event log for this series.
-            use crate::db::schema::event_logs::dsl as e;
-            let event_log_opt = e::event_logs
-                .filter(e::series_id.eq(&series_id_val))
-                .first::<crate::db::models::EventLog>(&mut connection)
-                .optional()
-                .map_err(|err| format!("Error querying event log: {}", err))?;
-
-            if let Some(event_log_record) = event_log_opt {
-                let banned_champs = extract_banned_champions(&event_log_record.event_log);
-                let banned_in_game = banned_filter
-                    .iter()
-                    .any(|champ| banned_champs.contains(champ));
-                if !banned_in_game {
-                    info!(
-                        "Series ID {} excluded because banned champions ({:?}) were not found in event log",
-                        series_entry.series_id, banned_champs
-                    );
-                    continue;
-                }
-            }
-        }
+#[command]
+pub async fn get_series_details(
+    database: State<'_, Database>,
+    series_id: String,
+) -> Result<SeriesWithParticipants, String> {
+    let mut connection = database.get()?;
+    hydrate_series(&mut connection, std::slice::from_ref(&series_id))
+        .map_err(|err| format!("Error loading series: {}", err))?
+        .pop()
+        .ok_or_else(|| format!("Series {} not found", series_id))
+}
 
-        // ---- Filter by Players ----
-        if !filters.players.is_empty() {
-            let allowed_player_names: Vec<String> =
-                filters.players.iter().map(|p| p.value.clone()).collect();
-            let player_found = all_participants
-                .iter()
-                .any(|p| allowed_player_names.contains(&p.player_name));
-            if !player_found {
-                info!(
-                    "Series ID {} excluded because none of the players match the filter",
-                    series_entry.series_id
-                );
-                continue;
-            }
-        }
+// Loads the given series with their participants and annotations, keeping the order of `series_ids`.
+fn hydrate_series(
+    connection: &mut SqliteConnection,
+    series_ids: &[String],
+) -> QueryResult<Vec<SeriesWithParticipants>> {
+    use crate::db::schema::games::dsl as g;
 
-        // If the series passed all filters, include it along with its participants.
-        results.push(SeriesWithParticipants {
-            series: series_entry,
-            participants: all_participants,
-        });
+    let mut series_by_id: HashMap<String, Series> = series
+        .filter(series_series_id.eq_any(series_ids))
+        .load::<Series>(connection)?
+        .into_iter()
+        .map(|series_entry| (series_entry.series_id.clone(), series_entry))
+        .collect();
+
+    let mut participants_by_series: HashMap<String, Vec<Participant>> = HashMap::new();
+    for participant in participants
+        .filter(participant_series_id.eq_any(series_ids))
+        .order(participant_id.asc())
+        .load::<Participant>(connection)?
+    {
+        participants_by_series
+            .entry(participant.series_id.clone())
+            .or_default()
+            .push(participant);
     }
 
-    if results.is_empty() {
-        return Err("No Series Found.".to_string());
+    let mut games_by_series: HashMap<String, Vec<crate::db::models::Game>> = HashMap::new();
+    for game in g::games
+        .filter(g::series_id.eq_any(series_ids))
+        .order(g::game.asc())
+        .load::<crate::db::models::Game>(connection)?
+    {
+        games_by_series
+            .entry(game.series_id.clone())
+            .or_default()
+            .push(game);
     }
-    results.sort_by(|a, b| {
-        match (
-            &a.series.start_time_scheduled,
-            &b.series.start_time_scheduled,
-        ) {
-            (Some(a_time), Some(b_time)) => b_time.cmp(a_time),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => std::cmp::Ordering::Equal,
-        }
-    });
 
-    Ok(results)
+    let mut annotations_by_series = annotations::load_for_series(connection, series_ids)?;
+
+    Ok(series_ids
+        .iter()
+        .filter_map(|series_id| {
+            let series_entry = series_by_id.remove(series_id)?;
+            Some(SeriesWithParticipants {
+                participants: participants_by_series.remove(series_id).unwrap_or_default(),
+                games: games_by_series.remove(series_id).unwrap_or_default(),
+                annotations: annotations_by_series.remove(series_id).unwrap_or_default(),
+                series: series_entry,
+            })
+        })
+        .collect())
 }
@@ -0,0 +1,129 @@
+// src-tauri/db/annotations.rs
+use crate::db::models::{Annotation, NewAnnotation, TaggedAnnotation};
+use crate::db::schema::annotation_tags::dsl as at;
+use crate::db::schema::annotations::dsl as a;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// What the UI sends when saving an annotation. Saving replaces the note, flag and tags
+/// of the existing annotation for the same series and game.
+#[derive(Debug, Deserialize)]
+pub struct AnnotationInput {
+    pub series_id: String,
+    #[serde(default)]
+    pub game: i32, // 0 for the whole series
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub reviewed: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn with_tags(
+    connection: &mut SqliteConnection,
+    annotations: Vec<Annotation>,
+) -> QueryResult<Vec<TaggedAnnotation>> {
+    let ids: Vec<i32> = annotations.iter().map(|annotation| annotation.id).collect();
+    let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+    for (annotation_id, tag) in at::annotation_tags
+        .filter(at::annotation_id.eq_any(&ids))
+        .order(at::tag.asc())
+        .load::<(i32, String)>(connection)?
+    {
+        tags.entry(annotation_id).or_default().push(tag);
+    }
+    Ok(annotations
+        .into_iter()
+        .map(|annotation| TaggedAnnotation {
+            tags: tags.remove(&annotation.id).unwrap_or_default(),
+            annotation,
+        })
+        .collect())
+}
+
+/// Annotations for the given series, keyed by series id and ordered by game.
+pub fn load_for_series(
+    connection: &mut SqliteConnection,
+    series_ids: &[String],
+) -> QueryResult<HashMap<String, Vec<TaggedAnnotation>>> {
+    let annotations = a::annotations
+        .filter(a::series_id.eq_any(series_ids))
+        .order((a::series_id.asc(), a::game.asc()))
+        .load::<Annotation>(connection)?;
+    let mut by_series: HashMap<String, Vec<TaggedAnnotation>> = HashMap::new();
+    for annotation in with_tags(connection, annotations)? {
+        by_series
+            .entry(annotation.annotation.series_id.clone())
+            .or_default()
+            .push(annotation);
+    }
+    Ok(by_series)
+}
+
+pub fn save(
+    connection: &mut SqliteConnection,
+    input: &AnnotationInput,
+) -> QueryResult<TaggedAnnotation> {
+    // Trimmed, de-duplicated and sorted so "Level 1 " and "Level 1" are one tag.
+    let tags: BTreeSet<&str> = input
+        .tags
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
+    connection.transaction(|conn| {
+        let annotation = diesel::insert_into(a::annotations)
+            .values(&NewAnnotation {
+                series_id: &input.series_id,
+                game: input.game,
+                note: &input.note,
+                reviewed: input.reviewed,
+            })
+            .on_conflict((a::series_id, a::game))
+            .do_update()
+            .set((
+                a::note.eq(excluded(a::note)),
+                a::reviewed.eq(excluded(a::reviewed)),
+                a::updated_at.eq(diesel::dsl::sql::<diesel::sql_types::Text>(
+                    "strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+                )),
+            ))
+            .get_result::<Annotation>(conn)?;
+
+        diesel::delete(at::annotation_tags.filter(at::annotation_id.eq(annotation.id)))
+            .execute(conn)?;
+        let rows: Vec<_> = tags
+            .iter()
+            .map(|tag| (at::annotation_id.eq(annotation.id), at::tag.eq(*tag)))
+            .collect();
+        diesel::insert_into(at::annotation_tags)
+            .values(&rows)
+            .execute(conn)?;
+
+        Ok(TaggedAnnotation {
+            annotation,
+            tags: tags.into_iter().map(String::from).collect(),
+        })
+    })
+}
+
+pub fn delete(connection: &mut SqliteConnection, annotation_id: i32) -> QueryResult<usize> {
+    connection.transaction(|conn| {
+        diesel::delete(at::annotation_tags.filter(at::annotation_id.eq(annotation_id)))
+            .execute(conn)?;
+        diesel::delete(a::annotations.find(annotation_id)).execute(conn)
+    })
+}
+
+/// Every tag in use, for autocomplete.
+pub fn all_tags(connection: &mut SqliteConnection) -> QueryResult<Vec<String>> {
+    at::annotation_tags
+        .select(at::tag)
+        .distinct()
+        .order(at::tag.asc())
+        .load(connection)
+}
@@ -0,0 +1,206 @@
+// src-tauri/db/backup.rs
+use super::{get_db_path, run_migrations, MIGRATIONS};
+use chrono::Local;
+use diesel::connection::SimpleConnection;
+use diesel::migration::MigrationSource;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel::sqlite::Sqlite;
+use log::{info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_DIR_NAME: &str = "snapshots";
+const MAX_SNAPSHOTS: usize = 5;
+const MIGRATIONS_TABLE: &str = "__diesel_schema_migrations";
+
+#[derive(QueryableByName)]
+struct NameRow {
+    #[diesel(sql_type = Text)]
+    name: String,
+}
+
+fn snapshot_dir() -> Result<PathBuf, String> {
+    let db_path = get_db_path()?;
+    let db_dir = db_path.parent().ok_or_else(|| {
+        format!(
+            "Database path {} has no parent directory",
+            db_path.display()
+        )
+    })?;
+    Ok(db_dir.join(SNAPSHOT_DIR_NAME))
+}
+
+/// Writes a consistent copy of the open database to `path`, replacing any existing file.
+/// `VACUUM INTO` reads a single snapshot, so a sync can keep writing while it runs.
+pub fn backup_to(connection: &mut SqliteConnection, path: &Path) -> Result<(), String> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+    }
+    // VACUUM INTO refuses to overwrite, so write next to the target and swap it in.
+    let partial_path = path.with_extension("partial");
+    if partial_path.exists() {
+        fs::remove_file(&partial_path)
+            .map_err(|err| format!("Failed to remove {}: {}", partial_path.display(), err))?;
+    }
+    let partial = partial_path
+        .to_str()
+        .ok_or_else(|| format!("Path {} is not valid UTF-8", partial_path.display()))?;
+    diesel::sql_query("VACUUM INTO ?")
+        .bind::<Text, _>(partial)
+        .execute(connection)
+        .map_err(|err| format!("Failed to back up database: {}", err))?;
+    fs::rename(&partial_path, path)
+        .map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+}
+
+/// Saves a timestamped copy of the database into the snapshots directory and prunes
+/// all but the newest few. Called before anything that deletes or rewrites data.
+pub fn snapshot(connection: &mut SqliteConnection, reason: &str) -> Result<PathBuf, String> {
+    let dir = snapshot_dir()?;
+    let path = dir.join(format!(
+        "database-{}-{}.sqlite",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        reason
+    ));
+    backup_to(connection, &path)?;
+    info!("Saved {} snapshot to {}", reason, path.display());
+    prune_snapshots(&dir);
+    Ok(path)
+}
+
+// Snapshot names start with their timestamp, so sorting by name sorts by age.
+fn prune_snapshots(dir: &Path) {
+    let mut snapshots: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sqlite"))
+            .collect(),
+        Err(err) => {
+            warn!("Failed to list snapshots in {}: {}", dir.display(), err);
+            return;
+        }
+    };
+    snapshots.sort();
+    let excess = snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+    for path in snapshots.into_iter().take(excess) {
+        if let Err(err) = fs::remove_file(&path) {
+            warn!("Failed to remove old snapshot {}: {}", path.display(), err);
+        }
+    }
+}
+
+fn applied_versions(connection: &mut SqliteConnection) -> Result<Vec<String>, String> {
+    let has_table =
+        diesel::sql_query("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind::<Text, _>(MIGRATIONS_TABLE)
+            .load::<NameRow>(connection)
+            .map_err(|err| format!("File is not a readable database: {}", err))?;
+    if has_table.is_empty() {
+        return Err("File is not a LOLScrimExporter database".to_string());
+    }
+    diesel::sql_query(format!("SELECT version AS name FROM {}", MIGRATIONS_TABLE))
+        .load::<NameRow>(connection)
+        .map(|rows| rows.into_iter().map(|row| row.name).collect())
+        .map_err(|err| format!("Failed to read schema version: {}", err))
+}
+
+fn known_versions() -> Result<Vec<String>, String> {
+    MigrationSource::<Sqlite>::migrations(&MIGRATIONS)
+        .map(|migrations| {
+            migrations
+                .iter()
+                .map(|migration| migration.name().version().to_string())
+                .collect()
+        })
+        .map_err(|err| format!("Failed to list migrations: {}", err))
+}
+
+/// Replaces the contents of the live database with a backup. The backup is copied and
+/// migrated to the current schema first, so older backups restore cleanly and a backup
+/// from a newer version of the app is rejected without touching the live data.
+pub fn restore_from(connection: &mut SqliteConnection, path: &Path) -> Result<usize, String> {
+    if !path.is_file() {
+        return Err(format!("Backup {} does not exist", path.display()));
+    }
+    let source_url = path
+        .to_str()
+        .ok_or_else(|| format!("Path {} is not valid UTF-8", path.display()))?;
+    let mut source = SqliteConnection::establish(source_url)
+        .map_err(|err| format!("Failed to open {}: {}", path.display(), err))?;
+
+    let known = known_versions()?;
+    let applied = applied_versions(&mut source)?;
+    if let Some(unknown) = applied.iter().find(|version| !known.contains(version)) {
+        return Err(format!(
+            "Backup uses schema version {} from a newer version of the app",
+            unknown
+        ));
+    }
+
+    // Migrate a scratch copy so the chosen file is never modified. It isn't named
+    // *.sqlite so snapshot pruning leaves it alone.
+    let staging_path = snapshot_dir()?.join("restore.staging");
+    backup_to(&mut source, &staging_path)?;
+    drop(source);
+    let staging_url = staging_path
+        .to_str()
+        .ok_or_else(|| format!("Path {} is not valid UTF-8", staging_path.display()))?;
+    let mut staging = SqliteConnection::establish(staging_url)
+        .map_err(|err| format!("Failed to open {}: {}", staging_path.display(), err))?;
+    run_migrations(&mut staging)?;
+    drop(staging);
+
+    snapshot(connection, "pre-restore")?;
+
+    diesel::sql_query("ATTACH DATABASE ? AS restore_source")
+        .bind::<Text, _>(staging_url)
+        .execute(connection)
+        .map_err(|err| format!("Failed to attach backup: {}", err))?;
+    let copied = copy_tables(connection);
+    if let Err(err) = connection.batch_execute("DETACH DATABASE restore_source") {
+        warn!("Failed to detach restored backup: {}", err);
+    }
+    if let Err(err) = fs::remove_file(&staging_path) {
+        warn!("Failed to remove {}: {}", staging_path.display(), err);
+    }
+    let copied = copied?;
+    info!("Restored {} rows from {}", copied, path.display());
+    Ok(copied)
+}
+
+// Copies every table from the attached backup over the live one in a single transaction.
+// Columns are listed explicitly because ADD COLUMN order can differ between databases.
+// The search index is virtual and is kept up to date by triggers as rows are copied.
+fn copy_tables(connection: &mut SqliteConnection) -> Result<usize, String> {
+    connection
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            let tables = diesel::sql_query(
+                "SELECT name FROM pragma_table_list
+                 WHERE schema = 'main' AND type = 'table'
+                   AND name NOT LIKE 'sqlite_%' AND name != ?",
+            )
+            .bind::<Text, _>(MIGRATIONS_TABLE)
+            .load::<NameRow>(conn)?;
+
+            let mut copied = 0;
+            for table in tables {
+                let columns = diesel::sql_query("SELECT name FROM pragma_table_info(?)")
+                    .bind::<Text, _>(&table.name)
+                    .load::<NameRow>(conn)?
+                    .into_iter()
+                    .map(|column| format!("\"{}\"", column.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                diesel::sql_query(format!("DELETE FROM main.\"{}\"", table.name)).execute(conn)?;
+                copied += diesel::sql_query(format!(
+                    "INSERT INTO main.\"{table}\" ({columns}) SELECT {columns} FROM restore_source.\"{table}\"",
+                    table = table.name,
+                ))
+                .execute(conn)?;
+            }
+            Ok(copied)
+        })
+        .map_err(|err| format!("Failed to restore backup: {}", err))
+}
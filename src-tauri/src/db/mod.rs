@@ -1,48 +1,187 @@
 // src-tauri/db/mod.rs
+pub mod annotations;
+pub mod backup;
 pub mod models;
+pub mod presets;
+pub mod purge;
 pub mod schema;
+pub mod search;
 
-use diesel::prelude::*;
+use crate::settings;
+use diesel::connection::SimpleConnection;
+use diesel::r2d2::{ConnectionManager, CustomizeConnection, Pool, PooledConnection};
 use diesel::sqlite::SqliteConnection;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use log::{info, warn};
+use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+const DB_PATH_ENV: &str = "LOLSCRIMEXPORTER_DB_PATH";
+const DB_FILE_NAME: &str = "database.sqlite";
+const APP_DIR_NAME: &str = "LOLScrimExporter";
+const POOL_SIZE: u32 = 8;
+const BUSY_TIMEOUT_MS: u32 = 5000;
 
-pub fn init() {
-    if !db_file_exists() {
-        create_db_file();
+pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
+pub type DbConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
+
+/// Shared database handle kept in Tauri managed state. Holds the startup error
+/// instead of a pool when the database couldn't be opened, so commands can report it.
+pub struct Database(Result<DbPool, String>);
+
+impl Database {
+    pub fn new(pool: Result<DbPool, String>) -> Self {
+        Database(pool)
+    }
+
+    pub fn pool(&self) -> Result<DbPool, String> {
+        self.0.clone()
+    }
+
+    pub fn get(&self) -> Result<DbConnection, String> {
+        match self.0 {
+            Ok(ref pool) => get_connection(pool),
+            Err(ref err) => Err(format!("Database unavailable: {}", err)),
+        }
     }
-    run_migrations();
 }
 
-pub fn establish_db_connection() -> SqliteConnection {
-    let db_path = get_db_path();
-    SqliteConnection::establish(&db_path)
-        .unwrap_or_else(|_| panic!("Error connecting to {}", db_path))
+// Applied to every pooled connection. WAL lets the UI read while a sync is writing,
+// and the busy timeout makes concurrent writers wait instead of failing immediately.
+#[derive(Debug)]
+struct ConnectionOptions;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ConnectionOptions {
+    fn on_acquire(&self, connection: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        connection
+            .batch_execute(&format!(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA synchronous = NORMAL;
+                 PRAGMA busy_timeout = {};",
+                BUSY_TIMEOUT_MS
+            ))
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+pub fn init() -> Result<DbPool, String> {
+    let db_path = get_db_path()?;
+    info!("Using database at {}", db_path.display());
+    if !db_path.exists() {
+        create_db_file(&db_path)?;
+    }
+    let pool = create_pool(&db_path)?;
+    let mut connection = get_connection(&pool)?;
+    snapshot_before_migrations(&mut connection);
+    run_migrations(&mut connection)?;
+    Ok(pool)
 }
 
-fn run_migrations() {
-    let mut connection = establish_db_connection();
-    connection.run_pending_migrations(MIGRATIONS).unwrap();
+pub fn get_connection(pool: &DbPool) -> Result<DbConnection, String> {
+    pool.get()
+        .map_err(|err| format!("Error getting a database connection: {}", err))
 }
 
-fn create_db_file() {
-    let db_path = get_db_path();
-    let db_dir = Path::new(&db_path).parent().unwrap();
-    if !db_dir.exists() {
-        fs::create_dir_all(db_dir).unwrap();
+fn create_pool(db_path: &Path) -> Result<DbPool, String> {
+    let db_url = db_path
+        .to_str()
+        .ok_or_else(|| format!("Database path {} is not valid UTF-8", db_path.display()))?;
+    Pool::builder()
+        .max_size(POOL_SIZE)
+        .connection_timeout(Duration::from_secs(30))
+        .connection_customizer(Box::new(ConnectionOptions))
+        .build(ConnectionManager::<SqliteConnection>::new(db_url))
+        .map_err(|err| format!("Error connecting to {}: {}", db_path.display(), err))
+}
+
+// Only existing databases get a snapshot; a fresh one has nothing to lose.
+fn snapshot_before_migrations(connection: &mut SqliteConnection) {
+    let has_data = connection
+        .applied_migrations()
+        .is_ok_and(|applied| !applied.is_empty());
+    let has_pending = connection
+        .has_pending_migration(MIGRATIONS)
+        .unwrap_or(false);
+    if has_data && has_pending {
+        if let Err(err) = backup::snapshot(connection, "pre-migration") {
+            warn!("Failed to snapshot database before migrating: {}", err);
+        }
     }
-    fs::File::create(db_path).unwrap();
 }
 
-fn db_file_exists() -> bool {
-    let db_path = get_db_path();
-    Path::new(&db_path).exists()
+fn run_migrations(connection: &mut SqliteConnection) -> Result<(), String> {
+    connection
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|err| format!("Error running database migrations: {}", err))?;
+    crate::sync::draft::backfill_drafts(connection)
+        .map(|_| ())
+        .map_err(|err| format!("Error backfilling drafts: {}", err))
+}
+
+fn create_db_file(db_path: &Path) -> Result<(), String> {
+    let db_dir = db_path.parent().ok_or_else(|| {
+        format!(
+            "Database path {} has no parent directory",
+            db_path.display()
+        )
+    })?;
+    fs::create_dir_all(db_dir)
+        .map_err(|err| format!("Failed to create {}: {}", db_dir.display(), err))?;
+    fs::File::create(db_path)
+        .map(|_| ())
+        .map_err(|err| format!("Failed to create {}: {}", db_path.display(), err))
 }
 
-fn get_db_path() -> String {
-    let home_dir = dirs::home_dir().unwrap();
-    home_dir.to_str().unwrap().to_string() + "/.config/LOLScrimExporter/database.sqlite"
+/// Resolves the database file: the environment override, then the configured
+/// path, then the platform data directory.
+pub fn get_db_path() -> Result<PathBuf, String> {
+    if let Some(path) = env::var_os(DB_PATH_ENV).filter(|path| !path.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    if let Some(path) = settings::load_or_default().database_path {
+        return Ok(PathBuf::from(path));
+    }
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| "Could not determine the platform data directory".to_string())?;
+    let db_path = data_dir.join(APP_DIR_NAME).join(DB_FILE_NAME);
+    if !db_path.exists() {
+        move_legacy_db(&db_path);
+    }
+    Ok(db_path)
+}
+
+// 0.1.x always stored the database under ~/.config, even on Windows and macOS.
+fn move_legacy_db(db_path: &Path) {
+    let Some(legacy_path) =
+        dirs::home_dir().map(|home| home.join(".config").join(APP_DIR_NAME).join(DB_FILE_NAME))
+    else {
+        return;
+    };
+    if legacy_path == db_path || !legacy_path.exists() {
+        return;
+    }
+    if let Some(db_dir) = db_path.parent() {
+        if let Err(err) = fs::create_dir_all(db_dir) {
+            warn!("Failed to create {}: {}", db_dir.display(), err);
+            return;
+        }
+    }
+    // Rename fails across filesystems, so fall back to copying.
+    let moved =
+        fs::rename(&legacy_path, db_path).or_else(|_| fs::copy(&legacy_path, db_path).map(|_| ()));
+    match moved {
+        Ok(()) => info!(
+            "Moved legacy database from {} to {}",
+            legacy_path.display(),
+            db_path.display()
+        ),
+        Err(err) => warn!(
+            "Failed to move legacy database from {}: {}",
+            legacy_path.display(),
+            err
+        ),
+    }
 }
@@ -1,7 +1,10 @@
 // src-tauri/db/models.rs
 
-use super::schema::{event_logs, participants, series};
-use diesel::{Insertable, Queryable, QueryableByName};
+use super::schema::{
+    annotations, draft_actions, event_logs, filter_presets, games, participants, players, series,
+    teams,
+};
+use diesel::{AsChangeset, Insertable, Queryable, QueryableByName};
 use serde::{Deserialize, Serialize};
 
 #[derive(Queryable, Serialize, Deserialize, Debug)]
@@ -19,6 +22,8 @@ pub struct Series {
     pub team2_score: Option<i32>,   // Now in correct position
     pub team2_name: Option<String>, // Now in correct position
     pub team2_logo: Option<String>,
+    pub patch_major: Option<i32>,
+    pub patch_minor: Option<i32>,
 }
 
 #[derive(Insertable, Debug)]
@@ -55,16 +60,74 @@ pub struct Participant {
     pub player_name: String,
     pub champion_name: String,
     pub stats_json: String, // Stores full stats as JSON
+    pub game: i32,
+    pub kills: Option<i32>,
+    pub deaths: Option<i32>,
+    pub assists: Option<i32>,
+    pub total_minions_killed: Option<i32>,
+    pub neutral_minions_killed: Option<i32>,
+    pub gold_earned: Option<i32>,
+    pub total_damage_dealt_to_champions: Option<i32>,
+    pub total_damage_taken: Option<i32>,
+    pub vision_score: Option<i32>,
+    pub wards_placed: Option<i32>,
+    pub wards_killed: Option<i32>,
+    pub detector_wards_placed: Option<i32>,
+    pub item0: Option<i32>,
+    pub item1: Option<i32>,
+    pub item2: Option<i32>,
+    pub item3: Option<i32>,
+    pub item4: Option<i32>,
+    pub item5: Option<i32>,
+    pub item6: Option<i32>,
+    pub primary_rune_style: Option<i32>,
+    pub keystone: Option<i32>,
+    pub secondary_rune_style: Option<i32>,
+    pub team_position: Option<String>,
+    pub team_id: Option<i32>,
+    pub win: Option<bool>,
+    pub puuid: Option<String>,
+    pub player_ref: Option<i32>,
+    pub role: Option<String>,
 }
 
-#[derive(Insertable, Debug)]
+#[derive(Insertable, AsChangeset, Debug)]
 #[diesel(table_name = participants)]
 pub struct NewParticipant {
     pub series_id: String,
+    pub game: i32,
     pub player_id: String,
     pub player_name: String,
     pub champion_name: String,
     pub stats_json: String, // Stores full stats as JSON
+    pub kills: Option<i32>,
+    pub deaths: Option<i32>,
+    pub assists: Option<i32>,
+    pub total_minions_killed: Option<i32>,
+    pub neutral_minions_killed: Option<i32>,
+    pub gold_earned: Option<i32>,
+    pub total_damage_dealt_to_champions: Option<i32>,
+    pub total_damage_taken: Option<i32>,
+    pub vision_score: Option<i32>,
+    pub wards_placed: Option<i32>,
+    pub wards_killed: Option<i32>,
+    pub detector_wards_placed: Option<i32>,
+    pub item0: Option<i32>,
+    pub item1: Option<i32>,
+    pub item2: Option<i32>,
+    pub item3: Option<i32>,
+    pub item4: Option<i32>,
+    pub item5: Option<i32>,
+    pub item6: Option<i32>,
+    pub primary_rune_style: Option<i32>,
+    pub keystone: Option<i32>,
+    pub secondary_rune_style: Option<i32>,
+    pub team_position: Option<String>,
+    pub team_id: Option<i32>,
+    pub win: Option<bool>,
+    pub puuid: Option<String>,
+    pub player_ref: Option<i32>,
+    pub role: Option<String>,
 }
 
 #[derive(Queryable, Serialize, Deserialize, Debug)]
@@ -80,3 +143,131 @@ pub struct NewEventLog<'a> {
     pub series_id: &'a str,
     pub event_log: &'a str,
 }
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct DraftAction {
+    pub id: i32,
+    pub series_id: String,
+    pub game: i32,
+    pub sequence: i32,
+    pub phase: i32,
+    pub side: String,
+    pub team_name: String,
+    pub action: String,
+    pub champion_name: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = draft_actions)]
+pub struct NewDraftAction {
+    pub series_id: String,
+    pub game: i32,
+    pub sequence: i32,
+    pub phase: i32,
+    pub side: String,
+    pub team_name: String,
+    pub action: String,
+    pub champion_name: String,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Team {
+    pub id: i32,
+    pub grid_id: String,
+    pub name: String,
+    pub logo_url: Option<String>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = teams)]
+pub struct NewTeam<'a> {
+    pub grid_id: &'a str,
+    pub name: &'a str,
+    pub logo_url: Option<&'a str>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Player {
+    pub id: i32,
+    pub puuid: Option<String>,
+    pub name: String,
+    pub merged_into: Option<i32>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = players)]
+pub struct NewPlayer<'a> {
+    pub puuid: Option<&'a str>,
+    pub name: &'a str,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlayerIdentity {
+    pub id: i32,
+    pub puuid: Option<String>,
+    pub name: String,
+    pub aliases: Vec<String>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Game {
+    pub id: i32,
+    pub series_id: String,
+    pub game: i32,
+    pub duration_seconds: Option<i32>,
+    pub blue_team_id: Option<String>,
+    pub red_team_id: Option<String>,
+    pub winning_side: Option<String>,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = games)]
+pub struct NewGame<'a> {
+    pub series_id: &'a str,
+    pub game: i32,
+    pub duration_seconds: Option<i32>,
+    pub winning_side: Option<&'a str>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug)]
+pub struct Annotation {
+    pub id: i32,
+    pub series_id: String,
+    pub game: i32, // 0 for the whole series
+    pub note: String,
+    pub reviewed: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = annotations)]
+pub struct NewAnnotation<'a> {
+    pub series_id: &'a str,
+    pub game: i32,
+    pub note: &'a str,
+    pub reviewed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TaggedAnnotation {
+    #[serde(flatten)]
+    pub annotation: Annotation,
+    pub tags: Vec<String>,
+}
+
+#[derive(Queryable, Debug)]
+pub struct FilterPresetRow {
+    pub id: i32,
+    pub name: String,
+    pub filters: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = filter_presets)]
+pub struct NewFilterPreset<'a> {
+    pub name: &'a str,
+    pub filters: &'a str,
+}
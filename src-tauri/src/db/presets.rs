@@ -0,0 +1,116 @@
+// src-tauri/db/presets.rs
+use crate::commands::FilterConfig;
+use crate::db::models::{FilterPresetRow, NewFilterPreset};
+use crate::db::schema::filter_presets::dsl as fp;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct FilterPreset {
+    pub id: i32,
+    pub name: String,
+    pub filters: FilterConfig,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl FilterPreset {
+    fn from_row(row: FilterPresetRow) -> Result<Self, String> {
+        let filters = serde_json::from_str(&row.filters)
+            .map_err(|err| format!("Preset '{}' has unreadable filters: {}", row.name, err))?;
+        Ok(FilterPreset {
+            id: row.id,
+            name: row.name,
+            filters,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Preset name can't be empty".to_string());
+    }
+    Ok(name)
+}
+
+fn to_json(filters: &FilterConfig) -> Result<String, String> {
+    serde_json::to_string(filters).map_err(|err| format!("Failed to save filters: {}", err))
+}
+
+fn save_error(name: &str, err: Error) -> String {
+    match err {
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            format!("A preset named '{}' already exists", name)
+        }
+        err => format!("Failed to save preset: {}", err),
+    }
+}
+
+/// All presets, by name.
+pub fn list(connection: &mut SqliteConnection) -> Result<Vec<FilterPreset>, String> {
+    fp::filter_presets
+        .order(fp::name.asc())
+        .load::<FilterPresetRow>(connection)
+        .map_err(|err| format!("Error querying presets: {}", err))?
+        .into_iter()
+        .map(FilterPreset::from_row)
+        .collect()
+}
+
+/// The preset with this name, ignoring case. This is how presets are referenced outside the UI.
+pub fn find_by_name(connection: &mut SqliteConnection, name: &str) -> Result<FilterPreset, String> {
+    let row = fp::filter_presets
+        .filter(fp::name.eq(name.trim()))
+        .first::<FilterPresetRow>(connection)
+        .optional()
+        .map_err(|err| format!("Error querying presets: {}", err))?
+        .ok_or_else(|| format!("No preset named '{}'", name.trim()))?;
+    FilterPreset::from_row(row)
+}
+
+pub fn create(
+    connection: &mut SqliteConnection,
+    name: &str,
+    filters: &FilterConfig,
+) -> Result<FilterPreset, String> {
+    let name = validate_name(name)?;
+    let row = diesel::insert_into(fp::filter_presets)
+        .values(&NewFilterPreset {
+            name,
+            filters: &to_json(filters)?,
+        })
+        .get_result::<FilterPresetRow>(connection)
+        .map_err(|err| save_error(name, err))?;
+    FilterPreset::from_row(row)
+}
+
+/// Renames a preset and replaces its filters.
+pub fn update(
+    connection: &mut SqliteConnection,
+    preset_id: i32,
+    name: &str,
+    filters: &FilterConfig,
+) -> Result<FilterPreset, String> {
+    let name = validate_name(name)?;
+    let row = diesel::update(fp::filter_presets.find(preset_id))
+        .set((
+            fp::name.eq(name),
+            fp::filters.eq(to_json(filters)?),
+            fp::updated_at.eq(diesel::dsl::sql::<diesel::sql_types::Text>(
+                "strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+            )),
+        ))
+        .get_result::<FilterPresetRow>(connection)
+        .optional()
+        .map_err(|err| save_error(name, err))?
+        .ok_or_else(|| format!("Preset {} not found", preset_id))?;
+    FilterPreset::from_row(row)
+}
+
+pub fn delete(connection: &mut SqliteConnection, preset_id: i32) -> QueryResult<usize> {
+    diesel::delete(fp::filter_presets.find(preset_id)).execute(connection)
+}
@@ -0,0 +1,254 @@
+// src-tauri/db/purge.rs
+use crate::db::backup;
+use crate::db::schema::draft_actions::dsl as d;
+use crate::db::schema::event_logs::dsl as e;
+use crate::db::schema::games::dsl as g;
+use crate::db::schema::participants::dsl as p;
+use crate::db::schema::series::dsl as s;
+use crate::patch::Patch;
+use crate::timestamp;
+use chrono::{Duration, NaiveDate};
+use diesel::prelude::*;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+// Keeps each DELETE well under SQLite's bound parameter limit.
+const DELETE_CHUNK_SIZE: usize = 500;
+
+// Teams no remaining series was played by.
+const ORPHANED_TEAMS: &str = "SELECT t.id FROM teams t WHERE NOT EXISTS (
+    SELECT 1 FROM series s WHERE t.grid_id IN (s.team1_id, s.team2_id))";
+
+// Players whose identity (the one they were merged into, if any) has no games left.
+const ORPHANED_PLAYERS: &str = "SELECT pl.id FROM players pl WHERE NOT EXISTS (
+    SELECT 1 FROM participants p WHERE p.player_ref = COALESCE(pl.merged_into, pl.id))";
+
+/// Which series to purge. Every field that is set must match; at least one is required.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PurgeScope {
+    pub from: Option<String>, // inclusive, compared against start_time_scheduled
+    pub to: Option<String>,   // exclusive
+    pub patch: Option<Patch>, // e.g. "14.10" also matches game version "14.10.123"
+    pub team_ids: Vec<String>, // GRID team ids, either side
+    pub series_ids: Vec<String>,
+}
+
+impl PurgeScope {
+    pub fn is_empty(&self) -> bool {
+        self.from.is_none()
+            && self.to.is_none()
+            && self.patch.is_none()
+            && self.team_ids.is_empty()
+            && self.series_ids.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct PurgeReport {
+    pub series: usize,
+    pub participants: usize,
+    pub event_logs: usize,
+    pub draft_actions: usize,
+    pub games: usize,
+    pub teams: usize,
+    pub players: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub max_age_days: Option<u32>,
+    pub keep_last_splits: Option<u32>,
+    pub split_start_dates: Vec<String>, // "YYYY-MM-DD", one per split
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|err| format!("Invalid date '{}' (expected YYYY-MM-DD): {}", value, err))
+}
+
+impl RetentionPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_age_days == Some(0) {
+            return Err("Retention must keep at least 1 day".to_string());
+        }
+        for date in &self.split_start_dates {
+            parse_date(date)?;
+        }
+        match self.keep_last_splits {
+            Some(0) => Err("Retention must keep at least 1 split".to_string()),
+            Some(_) if self.split_start_dates.is_empty() => {
+                Err("Keeping the last splits requires split start dates".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The date before which series are dropped, if any policy applies. With both
+    /// policies set the earlier cutoff wins, so data is only removed once both agree.
+    pub fn cutoff(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let by_age = self
+            .max_age_days
+            .map(|days| today - Duration::days(days as i64));
+        let by_splits = self.keep_last_splits.and_then(|keep| {
+            let mut started: Vec<NaiveDate> = self
+                .split_start_dates
+                .iter()
+                .filter_map(|date| parse_date(date).ok())
+                .filter(|date| *date <= today)
+                .collect();
+            started.sort();
+            started
+                .len()
+                .checked_sub(keep as usize)
+                .map(|index| started[index])
+        });
+        match (by_age, by_splits) {
+            (Some(age), Some(splits)) => Some(age.min(splits)),
+            (age, splits) => age.or(splits),
+        }
+    }
+}
+
+/// Series ids matching `scope`.
+pub fn matching_series(
+    connection: &mut SqliteConnection,
+    scope: &PurgeScope,
+) -> Result<Vec<String>, String> {
+    if scope.is_empty() {
+        return Err("Choose at least one date, patch, team or series to purge".to_string());
+    }
+    let mut query = s::series.select(s::series_id).into_boxed();
+    // Start times are stored in UTC, so timestamps with another offset are converted first.
+    let utc = |time: &String| timestamp::normalize(time).unwrap_or_else(|| time.clone());
+    if let Some(ref from) = scope.from {
+        query = query.filter(s::start_time_scheduled.ge(utc(from)));
+    }
+    if let Some(ref to) = scope.to {
+        query = query.filter(s::start_time_scheduled.lt(utc(to)));
+    }
+    if let Some(patch) = scope.patch {
+        query = query
+            .filter(s::patch_major.eq(patch.major))
+            .filter(s::patch_minor.eq(patch.minor));
+    }
+    if !scope.team_ids.is_empty() {
+        query = query.filter(
+            s::team1_id
+                .eq_any(scope.team_ids.clone())
+                .or(s::team2_id.eq_any(scope.team_ids.clone())),
+        );
+    }
+    if !scope.series_ids.is_empty() {
+        query = query.filter(s::series_id.eq_any(scope.series_ids.clone()));
+    }
+    query
+        .load::<String>(connection)
+        .map_err(|err| format!("Error querying series: {}", err))
+}
+
+/// Deletes the given series and every synced row that belongs to them in one transaction,
+/// along with the teams and players no remaining series references. Their names go too,
+/// which the search index triggers follow. Annotations are user data and stay, so they
+/// reattach if the series is synced again.
+pub fn purge_series(
+    connection: &mut SqliteConnection,
+    series_ids: &[String],
+) -> Result<PurgeReport, String> {
+    connection
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut report = PurgeReport::default();
+            for chunk in series_ids.chunks(DELETE_CHUNK_SIZE) {
+                report.participants +=
+                    diesel::delete(p::participants.filter(p::series_id.eq_any(chunk)))
+                        .execute(conn)?;
+                report.event_logs +=
+                    diesel::delete(e::event_logs.filter(e::series_id.eq_any(chunk)))
+                        .execute(conn)?;
+                report.draft_actions +=
+                    diesel::delete(d::draft_actions.filter(d::series_id.eq_any(chunk)))
+                        .execute(conn)?;
+                report.games +=
+                    diesel::delete(g::games.filter(g::series_id.eq_any(chunk))).execute(conn)?;
+                report.series +=
+                    diesel::delete(s::series.filter(s::series_id.eq_any(chunk))).execute(conn)?;
+            }
+            report.teams = delete_orphans(conn, "team_names", "team_id", "teams", ORPHANED_TEAMS)?;
+            report.players = delete_orphans(
+                conn,
+                "player_names",
+                "player_id",
+                "players",
+                ORPHANED_PLAYERS,
+            )?;
+            Ok(report)
+        })
+        .map_err(|err| format!("Failed to purge series: {}", err))
+}
+
+// Names go first, while the orphan query still sees the rows they belong to. Foreign keys
+// aren't enforced, so ON DELETE CASCADE wouldn't remove them.
+fn delete_orphans(
+    conn: &mut SqliteConnection,
+    names_table: &str,
+    owner_column: &str,
+    table: &str,
+    orphans: &str,
+) -> QueryResult<usize> {
+    diesel::sql_query(format!(
+        "DELETE FROM {names_table} WHERE {owner_column} IN ({orphans})"
+    ))
+    .execute(conn)?;
+    diesel::sql_query(format!("DELETE FROM {table} WHERE id IN ({orphans})")).execute(conn)
+}
+
+/// Purges series played before the retention cutoff, snapshotting first if any go.
+pub fn apply_retention(
+    connection: &mut SqliteConnection,
+    policy: &RetentionPolicy,
+    today: NaiveDate,
+) -> Result<PurgeReport, String> {
+    let Some(cutoff) = policy.cutoff(today) else {
+        return Ok(PurgeReport::default());
+    };
+    let scope = PurgeScope {
+        to: Some(cutoff.format("%Y-%m-%d").to_string()),
+        ..PurgeScope::default()
+    };
+    let expired = matching_series(connection, &scope)?;
+    if expired.is_empty() {
+        return Ok(PurgeReport::default());
+    }
+    backup::snapshot(connection, "pre-retention")?;
+    let report = purge_series(connection, &expired)?;
+    info!(
+        "Retention removed {} series played before {}",
+        report.series, cutoff
+    );
+    Ok(report)
+}
+
+/// Empties every table filled by syncing, including the team and player identities.
+/// Annotations are kept.
+pub fn clear_all(connection: &mut SqliteConnection) -> Result<PurgeReport, String> {
+    use crate::db::schema::{player_names, players, team_names, teams};
+
+    connection
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut report = PurgeReport {
+                participants: diesel::delete(p::participants).execute(conn)?,
+                event_logs: diesel::delete(e::event_logs).execute(conn)?,
+                draft_actions: diesel::delete(d::draft_actions).execute(conn)?,
+                games: diesel::delete(g::games).execute(conn)?,
+                series: diesel::delete(s::series).execute(conn)?,
+                ..PurgeReport::default()
+            };
+            diesel::delete(player_names::table).execute(conn)?;
+            report.players = diesel::delete(players::table).execute(conn)?;
+            diesel::delete(team_names::table).execute(conn)?;
+            report.teams = diesel::delete(teams::table).execute(conn)?;
+            Ok(report)
+        })
+        .map_err(|err| format!("Failed to clear database: {}", err))
+}
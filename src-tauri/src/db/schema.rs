@@ -1,6 +1,7 @@
 // src-tauri/db/schema.rs
 
 use diesel::allow_tables_to_appear_in_same_query;
+use diesel::joinable;
 use diesel::table;
 
 // src-tauri/db/schema.rs
@@ -20,6 +21,8 @@ table! {
         team2_score -> Nullable<Integer>,
         team2_name -> Nullable<Text>,
         team2_logo -> Nullable<Text>,
+        patch_major -> Nullable<Integer>, // `patch` as a number, NULL while it's unknown
+        patch_minor -> Nullable<Integer>,
     }
 }
 
@@ -31,6 +34,35 @@ table! {
         player_name -> Text,
         champion_name -> Text,
         stats_json -> Text, // Add stats_json column
+        game -> Integer,    // 1-based game number within the series
+        kills -> Nullable<Integer>,
+        deaths -> Nullable<Integer>,
+        assists -> Nullable<Integer>,
+        total_minions_killed -> Nullable<Integer>,
+        neutral_minions_killed -> Nullable<Integer>,
+        gold_earned -> Nullable<Integer>,
+        total_damage_dealt_to_champions -> Nullable<Integer>,
+        total_damage_taken -> Nullable<Integer>,
+        vision_score -> Nullable<Integer>,
+        wards_placed -> Nullable<Integer>,
+        wards_killed -> Nullable<Integer>,
+        detector_wards_placed -> Nullable<Integer>,
+        item0 -> Nullable<Integer>,
+        item1 -> Nullable<Integer>,
+        item2 -> Nullable<Integer>,
+        item3 -> Nullable<Integer>,
+        item4 -> Nullable<Integer>,
+        item5 -> Nullable<Integer>,
+        item6 -> Nullable<Integer>,
+        primary_rune_style -> Nullable<Integer>,
+        keystone -> Nullable<Integer>,
+        secondary_rune_style -> Nullable<Integer>,
+        team_position -> Nullable<Text>, // TOP, JUNGLE, MIDDLE, BOTTOM or UTILITY
+        team_id -> Nullable<Integer>,      // 100 (blue) or 200 (red)
+        win -> Nullable<Bool>,
+        puuid -> Nullable<Text>,
+        player_ref -> Nullable<Integer>, // players.id
+        role -> Nullable<Text>,          // team_position, or inferred when Riot left it empty
     }
 }
 
@@ -42,4 +74,112 @@ table! {
     }
 }
 
-allow_tables_to_appear_in_same_query!(series, participants, event_logs);
+table! {
+    draft_actions (id) {
+        id -> Integer,
+        series_id -> Text,
+        game -> Integer,         // 1-based game number within the series
+        sequence -> Integer,     // Order of the action within the game's draft
+        phase -> Integer,        // 1 or 2 (first/second ban or pick phase)
+        side -> Text,            // "blue" or "red"
+        team_name -> Text,
+        action -> Text,          // "ban" or "pick"
+        champion_name -> Text,
+    }
+}
+
+table! {
+    teams (id) {
+        id -> Integer,
+        grid_id -> Text,        // GRID team id, referenced by series.team1_id/team2_id
+        name -> Text,           // Most recently seen name
+        logo_url -> Nullable<Text>,
+    }
+}
+
+table! {
+    team_names (team_id, name) {
+        team_id -> Integer,
+        name -> Text,
+        first_seen -> Nullable<Text>,
+        last_seen -> Nullable<Text>,
+    }
+}
+
+table! {
+    players (id) {
+        id -> Integer,
+        puuid -> Nullable<Text>,           // Riot puuid
+        name -> Text,                      // Most recently seen Riot ID game name
+        merged_into -> Nullable<Integer>,  // Set once merged into another identity
+    }
+}
+
+table! {
+    player_names (player_id, name) {
+        player_id -> Integer,
+        name -> Text,
+        first_seen -> Nullable<Text>,
+        last_seen -> Nullable<Text>,
+    }
+}
+
+table! {
+    games (id) {
+        id -> Integer,
+        series_id -> Text,
+        game -> Integer,                       // 1-based game number within the series
+        duration_seconds -> Nullable<Integer>,
+        blue_team_id -> Nullable<Text>,        // GRID team id, from the draft
+        red_team_id -> Nullable<Text>,
+        winning_side -> Nullable<Text>,        // 'blue' or 'red'; NULL for remakes
+    }
+}
+
+table! {
+    annotations (id) {
+        id -> Integer,
+        series_id -> Text,
+        game -> Integer,         // 0 for the whole series
+        note -> Text,
+        reviewed -> Bool,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+table! {
+    annotation_tags (annotation_id, tag) {
+        annotation_id -> Integer,
+        tag -> Text,
+    }
+}
+
+table! {
+    filter_presets (id) {
+        id -> Integer,
+        name -> Text,    // unique, case-insensitively
+        filters -> Text, // FilterConfig as JSON
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+joinable!(team_names -> teams (team_id));
+joinable!(annotation_tags -> annotations (annotation_id));
+joinable!(player_names -> players (player_id));
+
+allow_tables_to_appear_in_same_query!(
+    series,
+    participants,
+    event_logs,
+    draft_actions,
+    teams,
+    team_names,
+    players,
+    player_names,
+    annotations,
+    annotation_tags,
+    games,
+    filter_presets
+);
@@ -0,0 +1,153 @@
+// src-tauri/db/search.rs
+use crate::db::schema::players::dsl as pl;
+use crate::db::schema::teams::dsl as t;
+use crate::sync::entities;
+use diesel::prelude::*;
+use diesel::sql_types::{Double, Integer, Nullable, Text};
+use serde::Serialize;
+use std::collections::HashSet;
+
+// Hits are fetched generously because aliases of one player or team collapse into a single result.
+const HITS_PER_RESULT: i32 = 4;
+
+#[derive(QueryableByName)]
+struct SearchHit {
+    #[diesel(sql_type = Text)]
+    kind: String,
+    #[diesel(sql_type = Nullable<Integer>)]
+    ref_id: Option<i32>,
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Double)]
+    score: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub kind: String,    // "player", "team" or "champion"
+    pub id: Option<i32>, // players.id / teams.id, None for champions
+    pub name: String,    // current name
+    pub matched: String, // the name or alias that matched
+    pub score: f64,      // bm25, lower is better
+}
+
+/// Turns free text into an FTS5 query where every word must match as a prefix.
+/// Words are quoted so user input can't inject FTS5 syntax.
+pub fn match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+// Hits containing every word anywhere, such as "ker" in "Faker", which prefix matching misses.
+// They rank after the FTS hits with a score of 0.
+fn substring_hits(
+    connection: &mut SqliteConnection,
+    query: &str,
+    kind: Option<&str>,
+    limit: i32,
+) -> QueryResult<Vec<SearchHit>> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| {
+            let escaped = term
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{}%", escaped)
+        })
+        .collect();
+    let conditions = vec!["name LIKE ? ESCAPE '\\'"; terms.len()].join(" AND ");
+    let mut hits = diesel::sql_query(format!(
+        "SELECT kind, ref_id, name, 0.0 AS score
+         FROM search_index
+         WHERE {conditions} AND (? IS NULL OR kind = ?)
+         ORDER BY length(name), name
+         LIMIT ?"
+    ))
+    .into_boxed();
+    for term in &terms {
+        hits = hits.bind::<Text, _>(term.clone());
+    }
+    hits.bind::<Nullable<Text>, _>(kind)
+        .bind::<Nullable<Text>, _>(kind)
+        .bind::<Integer, _>(limit)
+        .load::<SearchHit>(connection)
+}
+
+/// Ranked search over player, team and champion names (and their aliases) and annotation notes.
+/// Word prefixes rank first, then names containing the words anywhere. Pass `kind` to
+/// restrict results to one kind.
+pub fn search(
+    connection: &mut SqliteConnection,
+    query: &str,
+    kind: Option<&str>,
+    limit: usize,
+) -> QueryResult<Vec<SearchResult>> {
+    let Some(expression) = match_expression(query) else {
+        return Ok(Vec::new());
+    };
+    let hits = diesel::sql_query(
+        "SELECT kind, ref_id, name, bm25(search_index) AS score
+         FROM search_index
+         WHERE search_index MATCH ? AND (? IS NULL OR kind = ?)
+         ORDER BY score
+         LIMIT ?",
+    )
+    .bind::<Text, _>(&expression)
+    .bind::<Nullable<Text>, _>(kind)
+    .bind::<Nullable<Text>, _>(kind)
+    .bind::<Integer, _>(limit as i32 * HITS_PER_RESULT)
+    .load::<SearchHit>(connection)?;
+    let hits = if hits.len() < limit {
+        let substrings = substring_hits(connection, query, kind, limit as i32 * HITS_PER_RESULT)?;
+        hits.into_iter().chain(substrings).collect()
+    } else {
+        hits
+    };
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for hit in hits {
+        let (id, name) = match (hit.kind.as_str(), hit.ref_id) {
+            ("player", Some(player_id)) => {
+                let player_id = entities::resolve_player(connection, player_id)?;
+                let name = pl::players
+                    .find(player_id)
+                    .select(pl::name)
+                    .first::<String>(connection)?;
+                (Some(player_id), name)
+            }
+            ("team", Some(team_id)) => {
+                let name = t::teams
+                    .find(team_id)
+                    .select(t::name)
+                    .first::<String>(connection)?;
+                (Some(team_id), name)
+            }
+            ("note", Some(annotation_id)) => (Some(annotation_id), hit.name.clone()),
+            _ => (None, hit.name.clone()),
+        };
+        // Hits are ordered best first, so the first alias to match represents the result.
+        if !seen.insert((hit.kind.clone(), id, name.clone())) {
+            continue;
+        }
+        results.push(SearchResult {
+            kind: hit.kind,
+            id,
+            name,
+            matched: hit.name,
+            score: hit.score,
+        });
+        if results.len() == limit {
+            break;
+        }
+    }
+    Ok(results)
+}
@@ -0,0 +1,805 @@
+// src-tauri/filters/mod.rs
+pub mod page;
+pub mod query;
+
+use crate::commands::{
+    ChampionSelection, FilterConfig, Matchup, Modes, ResultScope, Side, TeamRelation,
+};
+use crate::patch::Patch;
+use crate::timestamp::{DateBound, Timezone};
+use chrono::Weekday;
+use diesel::prelude::*;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
+use diesel::sql_types::{BigInt, Integer, Text};
+use diesel::sqlite::Sqlite;
+use serde::Serialize;
+
+pub enum SqlValue {
+    Text(String),
+    Integer(i32),
+}
+
+/// WHERE conditions over the `series` table plus their bound parameters, ANDed together.
+/// Conditions on related tables are correlated subqueries against `series.series_id`.
+/// Each condition is tagged with the filter stage it came from for diagnostics.
+#[derive(Default)]
+pub struct SqlFilter {
+    conditions: Vec<Condition>,
+}
+
+struct Condition {
+    stage: &'static str,
+    sql: String,
+    params: Vec<SqlValue>,
+}
+
+impl SqlFilter {
+    pub fn push(
+        &mut self,
+        stage: &'static str,
+        condition: impl Into<String>,
+        params: impl IntoIterator<Item = SqlValue>,
+    ) {
+        self.conditions.push(Condition {
+            stage,
+            sql: condition.into(),
+            params: params.into_iter().collect(),
+        });
+    }
+
+    pub fn where_clause(&self) -> String {
+        where_clause(&self.conditions)
+    }
+
+    pub fn bind<'a>(
+        &'a self,
+        query: BoxedSqlQuery<'a, Sqlite, SqlQuery>,
+    ) -> BoxedSqlQuery<'a, Sqlite, SqlQuery> {
+        bind_values(query, self.params())
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = &SqlValue> {
+        self.conditions
+            .iter()
+            .flat_map(|condition| &condition.params)
+    }
+}
+
+fn where_clause(conditions: &[Condition]) -> String {
+    if conditions.is_empty() {
+        "1 = 1".to_string()
+    } else {
+        conditions
+            .iter()
+            .map(|condition| format!("({})", condition.sql))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+}
+
+pub fn bind_values<'a>(
+    mut query: BoxedSqlQuery<'a, Sqlite, SqlQuery>,
+    params: impl IntoIterator<Item = &'a SqlValue>,
+) -> BoxedSqlQuery<'a, Sqlite, SqlQuery> {
+    for param in params {
+        query = match param {
+            SqlValue::Text(value) => query.bind::<Text, _>(value),
+            SqlValue::Integer(value) => query.bind::<Integer, _>(*value),
+        };
+    }
+    query
+}
+
+pub fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn texts(values: &[String]) -> Vec<SqlValue> {
+    values.iter().cloned().map(SqlValue::Text).collect()
+}
+
+/// Filter inputs that need the database (or GRID) to resolve before building SQL.
+#[derive(Default)]
+pub struct ResolvedFilters {
+    pub my_team_ids: Vec<String>, // empty when my team couldn't be determined
+    pub team_ids: Vec<String>,    // GRID ids of the selected teams, including past names
+    pub player_refs: Vec<i32>,    // players.id of the selected players, including merged ones
+}
+
+impl ResolvedFilters {
+    pub fn resolve(
+        connection: &mut SqliteConnection,
+        filters: &FilterConfig,
+        my_team_ids: Vec<String>,
+    ) -> QueryResult<Self> {
+        let team_names: Vec<String> = filters.teams.iter().map(|t| t.value.clone()).collect();
+        let player_names: Vec<String> = filters.players.iter().map(|p| p.value.clone()).collect();
+        Ok(ResolvedFilters {
+            my_team_ids,
+            team_ids: resolve_team_grid_ids(connection, &team_names)?,
+            player_refs: resolve_player_refs(connection, &player_names)?,
+        })
+    }
+
+    /// The teams "us" refers to: my teams when known, otherwise any selected team.
+    pub fn our_team_ids(&self) -> Vec<String> {
+        if self.my_team_ids.is_empty() {
+            self.team_ids.clone()
+        } else {
+            self.my_team_ids.clone()
+        }
+    }
+}
+
+// Team names (current or historical) selected in the filter, as GRID team ids.
+fn resolve_team_grid_ids(
+    connection: &mut SqliteConnection,
+    names: &[String],
+) -> QueryResult<Vec<String>> {
+    use crate::db::schema::team_names::dsl as tn;
+    use crate::db::schema::teams::dsl as t;
+
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    t::teams
+        .filter(
+            t::name.eq_any(names).or(diesel::dsl::exists(
+                tn::team_names
+                    .filter(tn::team_id.eq(t::id))
+                    .filter(tn::name.eq_any(names)),
+            )),
+        )
+        .select(t::grid_id)
+        .load::<String>(connection)
+}
+
+// Player names (current or historical) selected in the filter, as player ids including
+// any identities merged into them.
+fn resolve_player_refs(
+    connection: &mut SqliteConnection,
+    names: &[String],
+) -> QueryResult<Vec<i32>> {
+    use crate::db::schema::player_names::dsl as pn;
+    use crate::db::schema::players::dsl as pl;
+
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let roots = pl::players
+        .filter(pl::merged_into.is_null())
+        .filter(
+            pl::name.eq_any(names).or(diesel::dsl::exists(
+                pn::player_names
+                    .filter(pn::player_id.eq(pl::id))
+                    .filter(pn::name.eq_any(names)),
+            )),
+        )
+        .select(pl::id)
+        .load::<i32>(connection)?;
+    let merged = pl::players
+        .filter(pl::merged_into.eq_any(&roots))
+        .select(pl::id)
+        .load::<i32>(connection)?;
+    Ok(roots.into_iter().chain(merged).collect())
+}
+
+// `blue` or `red` depending on the side our team played in game `g`, NULL when neither
+// side is ours.
+fn our_side(our_teams: &[String], blue: &str, red: &str) -> (String, Vec<SqlValue>) {
+    let teams = placeholders(our_teams.len());
+    let mut params = texts(our_teams);
+    params.extend(texts(our_teams));
+    (
+        format!(
+            "CASE WHEN g.blue_team_id IN ({teams}) THEN {blue}
+                  WHEN g.red_team_id IN ({teams}) THEN {red} END"
+        ),
+        params,
+    )
+}
+
+// Condition on a participant `p` (with its game `g`) for one picked champion. The team is
+// matched through the side each team played; with no team known, `build` empties the list
+// in the picked_by_team stage instead.
+fn participant_match(
+    selection: &ChampionSelection,
+    our_teams: &[String],
+) -> (String, Vec<SqlValue>) {
+    let mut conditions = vec!["p.champion_name = ?".to_string()];
+    let mut params = vec![SqlValue::Text(selection.champ.clone())];
+    if let Some(role) = selection.role {
+        conditions.push("p.role = ?".to_string());
+        params.push(SqlValue::Text(role.as_str().to_string()));
+    }
+    if let (Some(team), false) = (selection.team, our_teams.is_empty()) {
+        let (our_side, side_params) = our_side(our_teams, "100", "200");
+        let cmp = match team {
+            TeamRelation::Us => "=",
+            TeamRelation::Opponent => "<>",
+        };
+        conditions.push(format!("p.team_id {cmp} {our_side}"));
+        params.extend(side_params);
+    }
+    (conditions.join(" AND "), params)
+}
+
+// Condition on a ban `d` (with its game `g`) for one banned champion. Like picks, a team
+// scope with no team known is left to `build`'s banned_by_team stage.
+fn ban_match(selection: &ChampionSelection, our_teams: &[String]) -> (String, Vec<SqlValue>) {
+    let mut conditions = vec!["d.champion_name = ?".to_string()];
+    let mut params = vec![SqlValue::Text(selection.champ.clone())];
+    if let Some(phase) = selection.phase {
+        conditions.push("d.phase = ?".to_string());
+        params.push(SqlValue::Integer(phase));
+    }
+    if let (Some(team), false) = (selection.team, our_teams.is_empty()) {
+        let (our_side, side_params) = our_side(our_teams, "'blue'", "'red'");
+        let cmp = match team {
+            TeamRelation::Us => "=",
+            TeamRelation::Opponent => "<>",
+        };
+        conditions.push(format!("d.side {cmp} {our_side}"));
+        params.extend(side_params);
+    }
+    (conditions.join(" AND "), params)
+}
+
+// Pushes per-champion conditions combined as `mode` asks. `exists` wraps a condition into
+// one on `series`; series matching `unknown` can't be judged and are kept by Any and Only.
+fn push_by_mode(
+    sql: &mut SqlFilter,
+    stage: &'static str,
+    mode: &Modes,
+    matches: Vec<(String, Vec<SqlValue>)>,
+    exists: impl Fn(&str) -> String,
+    unknown: Option<&str>,
+) {
+    let keep_unknown = |condition: String| match unknown {
+        Some(unknown) => format!("{unknown} OR {condition}"),
+        None => condition,
+    };
+    let any = |matches: Vec<(String, Vec<SqlValue>)>| {
+        let condition = matches
+            .iter()
+            .map(|(condition, _)| format!("({condition})"))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let params: Vec<SqlValue> = matches.into_iter().flat_map(|(_, params)| params).collect();
+        (exists(&condition), params)
+    };
+    match mode {
+        Modes::Any => {
+            let (condition, params) = any(matches);
+            sql.push(stage, keep_unknown(condition), params);
+        }
+        Modes::Only => {
+            for (condition, params) in matches {
+                sql.push(stage, keep_unknown(exists(&condition)), params);
+            }
+        }
+        Modes::None => {
+            let (condition, params) = any(matches);
+            sql.push(stage, format!("NOT {condition}"), params);
+        }
+    }
+}
+
+// Series with a game where our `us` faced their `them` in the same role. Without a known
+// team either team counts as ours. None when the matchup names no champion.
+fn matchup_match(matchup: &Matchup, our_teams: &[String]) -> Option<(String, Vec<SqlValue>)> {
+    if matchup.ours.is_none() && matchup.theirs.is_none() {
+        return None;
+    }
+    let mut conditions = vec!["us.series_id = series.series_id".to_string()];
+    let mut params = Vec::new();
+    if !our_teams.is_empty() {
+        let (our_side, side_params) = our_side(our_teams, "100", "200");
+        conditions.push(format!("us.team_id = {our_side}"));
+        params.extend(side_params);
+    }
+    for (column, value) in [
+        ("us.champion_name", &matchup.ours),
+        ("them.champion_name", &matchup.theirs),
+    ] {
+        if let Some(champion) = value {
+            conditions.push(format!("{column} = ?"));
+            params.push(SqlValue::Text(champion.clone()));
+        }
+    }
+    if let Some(role) = matchup.role {
+        conditions.push("us.role = ?".to_string());
+        params.push(SqlValue::Text(role.as_str().to_string()));
+    }
+    Some((
+        format!(
+            "EXISTS (SELECT 1 FROM participants us
+                     JOIN participants them ON them.series_id = us.series_id
+                          AND them.game = us.game AND them.role = us.role
+                          AND them.team_id <> us.team_id
+                     LEFT JOIN games g ON g.series_id = us.series_id AND g.game = us.game
+                     WHERE {})",
+            conditions.join(" AND ")
+        ),
+        params,
+    ))
+}
+
+// Compares the series' patch with `patch`, e.g. `>=` for "14.10 or later". Series with an
+// unknown patch never match.
+fn patch_condition(op: &str, patch: Patch) -> (String, Vec<SqlValue>) {
+    (
+        format!("(series.patch_major, series.patch_minor) {op} (?, ?)"),
+        vec![
+            SqlValue::Integer(patch.major),
+            SqlValue::Integer(patch.minor),
+        ],
+    )
+}
+
+// Series starting before or after `bound`. Days compare in `timezone`; instants compare
+// against the stored UTC start time.
+fn date_condition(op: &str, bound: DateBound, timezone: Timezone) -> (String, Vec<SqlValue>) {
+    match bound {
+        DateBound::Day(_) => (
+            format!("date(series.start_time_scheduled, ?) {op} ?"),
+            vec![
+                SqlValue::Text(timezone.modifier()),
+                SqlValue::Text(bound.to_string()),
+            ],
+        ),
+        DateBound::Instant(_) => (
+            format!("series.start_time_scheduled {op} ?"),
+            vec![SqlValue::Text(bound.to_string())],
+        ),
+    }
+}
+
+// Series starting on any of `weekdays`, in `timezone`. strftime's %w counts from Sunday.
+fn weekday_condition(weekdays: &[Weekday], timezone: Timezone) -> (String, Vec<SqlValue>) {
+    let mut params = vec![SqlValue::Text(timezone.modifier())];
+    params.extend(
+        weekdays
+            .iter()
+            .map(|day| SqlValue::Integer(day.num_days_from_sunday() as i32)),
+    );
+    (
+        format!(
+            "CAST(strftime('%w', series.start_time_scheduled, ?) AS INTEGER) IN ({})",
+            placeholders(weekdays.len())
+        ),
+        params,
+    )
+}
+
+// Series with both teams and scores known, the only ones a result can be judged on.
+const COMPLETE_RESULT: &str = "series.team1_id IS NOT NULL AND series.team2_id IS NOT NULL
+     AND series.team1_score IS NOT NULL AND series.team2_score IS NOT NULL";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    Lost,
+    Drawn,
+}
+
+// Series my team won, lost or drew by games won. Needs COMPLETE_RESULT alongside.
+fn result_condition(my_teams: &[String], outcome: Outcome) -> (String, Vec<SqlValue>) {
+    let cmp = match outcome {
+        Outcome::Won => ">",
+        Outcome::Lost => "<",
+        Outcome::Drawn => "=",
+    };
+    let teams = placeholders(my_teams.len());
+    (
+        format!(
+            "(series.team1_id IN ({teams}) AND series.team1_score {cmp} series.team2_score)
+             OR (series.team2_id IN ({teams}) AND series.team2_score {cmp} series.team1_score)"
+        ),
+        texts(my_teams).into_iter().chain(texts(my_teams)).collect(),
+    )
+}
+
+// Series with a game whose winner and sides are known. Sides come from the draft, so series
+// synced without one can't be judged game by game; diagnostics list them as their own stage.
+const GAME_RESULT: &str = "EXISTS (SELECT 1 FROM games g
+     WHERE g.series_id = series.series_id AND g.winning_side IS NOT NULL
+       AND g.blue_team_id IS NOT NULL AND g.red_team_id IS NOT NULL)";
+
+// Series with a game my team won (or lost).
+fn game_result_condition(my_teams: &[String], won: bool) -> (String, Vec<SqlValue>) {
+    let (side, params) = our_side(my_teams, "'blue'", "'red'");
+    let cmp = if won { "=" } else { "<>" };
+    (
+        format!(
+            "EXISTS (SELECT 1 FROM games g WHERE g.series_id = series.series_id
+                     AND g.winning_side {cmp} {side})"
+        ),
+        params,
+    )
+}
+
+// Series involving any of the teams, by GRID id or by the name stored on the series.
+fn team_condition(team_ids: &[String], names: &[String]) -> (String, Vec<SqlValue>) {
+    let ids = placeholders(team_ids.len());
+    let name_list = placeholders(names.len());
+    let mut params = texts(team_ids);
+    params.extend(texts(team_ids));
+    params.extend(texts(names));
+    params.extend(texts(names));
+    (
+        format!(
+            "series.team1_id IN ({ids}) OR series.team2_id IN ({ids})
+             OR series.team1_name IN ({name_list}) OR series.team2_name IN ({name_list})"
+        ),
+        params,
+    )
+}
+
+fn player_condition(player_refs: &[i32], names: &[String]) -> (String, Vec<SqlValue>) {
+    let mut params: Vec<SqlValue> = player_refs
+        .iter()
+        .map(|player_ref| SqlValue::Integer(*player_ref))
+        .collect();
+    params.extend(texts(names));
+    (
+        format!(
+            "EXISTS (SELECT 1 FROM participants p
+                     WHERE p.series_id = series.series_id
+                       AND (p.player_ref IN ({}) OR p.player_name IN ({})))",
+            placeholders(player_refs.len()),
+            placeholders(names.len())
+        ),
+        params,
+    )
+}
+
+// Series where any of the teams played a game on `side`.
+fn side_condition(side: Side, team_ids: &[String]) -> (String, Vec<SqlValue>) {
+    let column = match side {
+        Side::Blue => "blue_team_id",
+        Side::Red => "red_team_id",
+    };
+    (
+        format!(
+            "EXISTS (SELECT 1 FROM games g
+                     WHERE g.series_id = series.series_id AND g.{column} IN ({}))",
+            placeholders(team_ids.len())
+        ),
+        texts(team_ids),
+    )
+}
+
+fn tags_condition(tags: &[String]) -> (String, Vec<SqlValue>) {
+    (
+        format!(
+            "EXISTS (SELECT 1 FROM annotations a
+                     JOIN annotation_tags t ON t.annotation_id = a.id
+                     WHERE a.series_id = series.series_id AND t.tag IN ({}))",
+            placeholders(tags.len())
+        ),
+        texts(tags),
+    )
+}
+
+fn reviewed_condition(reviewed: bool) -> String {
+    let negate = if reviewed { "" } else { "NOT " };
+    format!(
+        "{negate}EXISTS (SELECT 1 FROM annotations a
+           WHERE a.series_id = series.series_id AND a.game = 0 AND a.reviewed)"
+    )
+}
+
+// Series with a participant matching `condition` (see `participant_match`).
+fn picked(condition: &str) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM participants p
+                 LEFT JOIN games g ON g.series_id = p.series_id AND g.game = p.game
+                 WHERE p.series_id = series.series_id AND ({condition}))"
+    )
+}
+
+// Series with a ban matching `condition` (see `ban_match`).
+fn banned(condition: &str) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM draft_actions d
+                 LEFT JOIN games g ON g.series_id = d.series_id AND g.game = d.game
+                 WHERE d.series_id = series.series_id AND d.action = 'ban' AND ({condition}))"
+    )
+}
+
+// Series without a parsed draft, whose bans are unknown.
+const NO_DRAFT: &str = "NOT EXISTS (SELECT 1 FROM draft_actions d
+                                    WHERE d.series_id = series.series_id AND d.action = 'ban')";
+
+/// Translates the sidebar filters into SQL conditions on `series`.
+pub fn build(filters: &FilterConfig, resolved: &ResolvedFilters) -> SqlFilter {
+    let mut sql = SqlFilter::default();
+
+    if !filters.patch.is_empty() {
+        if let Some(patch) = Patch::parse(&filters.patch) {
+            let (condition, params) = patch_condition("=", patch);
+            sql.push("patch", condition, params);
+        } else if let Some(major) = Patch::parse_major(&filters.patch) {
+            // "14" is every 14.x patch.
+            sql.push(
+                "patch",
+                "series.patch_major = ?",
+                [SqlValue::Integer(major)],
+            );
+        } else {
+            // Placeholders like "latest" only match themselves.
+            sql.push(
+                "patch",
+                "series.patch = ?",
+                [SqlValue::Text(filters.patch.clone())],
+            );
+        }
+    }
+    if !filters.patches.is_empty() {
+        sql.push(
+            "patch",
+            format!(
+                "(series.patch_major, series.patch_minor) IN (VALUES {})",
+                vec!["(?, ?)"; filters.patches.len()].join(", ")
+            ),
+            filters.patches.iter().flat_map(|patch| {
+                [
+                    SqlValue::Integer(patch.major),
+                    SqlValue::Integer(patch.minor),
+                ]
+            }),
+        );
+    }
+    for (op, bound) in [
+        (">=", filters.patch_range.from),
+        ("<=", filters.patch_range.to),
+    ] {
+        if let Some(patch) = bound {
+            let (condition, params) = patch_condition(op, patch);
+            sql.push("patch", condition, params);
+        }
+    }
+    if let Some(count) = filters.last_patches {
+        sql.push(
+            "patch",
+            "(series.patch_major, series.patch_minor) IN (
+                 SELECT DISTINCT patch_major, patch_minor FROM series
+                 WHERE patch_major IS NOT NULL
+                 ORDER BY patch_major DESC, patch_minor DESC
+                 LIMIT ?)",
+            [SqlValue::Integer(i32::from(count))],
+        );
+    }
+
+    for (op, bound) in [
+        (">=", filters.date_range.from),
+        ("<=", filters.date_range.to),
+    ] {
+        if let Some(bound) = bound {
+            let (condition, params) = date_condition(op, bound, filters.timezone);
+            sql.push("date", condition, params);
+        }
+    }
+    if !filters.weekdays.is_empty() {
+        let (condition, params) = weekday_condition(&filters.weekdays, filters.timezone);
+        sql.push("date", condition, params);
+    }
+
+    let (scope_stage, scope_condition, wanted) = match filters.result_scope {
+        ResultScope::Series => (
+            "result",
+            COMPLETE_RESULT,
+            vec![
+                (filters.wins, Outcome::Won),
+                (filters.losses, Outcome::Lost),
+                (filters.draws, Outcome::Drawn),
+            ],
+        ),
+        ResultScope::Game => (
+            "known_sides",
+            GAME_RESULT,
+            vec![
+                (filters.wins, Outcome::Won),
+                (filters.losses, Outcome::Lost),
+            ],
+        ),
+    };
+    if wanted.iter().any(|(shown, _)| *shown) {
+        sql.push(scope_stage, scope_condition, []);
+        // Without a known team the result filter can't apply, so only series with results are
+        // kept. Neither does it when every outcome is shown.
+        if filters.filters_by_result() && !resolved.my_team_ids.is_empty() {
+            let mut outcomes = Vec::new();
+            let mut params = Vec::new();
+            for (_, outcome) in wanted.into_iter().filter(|(shown, _)| *shown) {
+                let (condition, outcome_params) = match filters.result_scope {
+                    ResultScope::Series => result_condition(&resolved.my_team_ids, outcome),
+                    ResultScope::Game => {
+                        game_result_condition(&resolved.my_team_ids, outcome == Outcome::Won)
+                    }
+                };
+                outcomes.push(condition);
+                params.extend(outcome_params);
+            }
+            sql.push("result", outcomes.join(" OR "), params);
+        }
+    }
+
+    if !filters.teams.is_empty() {
+        let names: Vec<String> = filters.teams.iter().map(|t| t.value.clone()).collect();
+        let (condition, params) = team_condition(&resolved.team_ids, &names);
+        sql.push("teams", condition, params);
+    }
+
+    // With no team known, no game has a side of ours, like the query language's error; the
+    // stage still shows in diagnostics as the one that emptied the list.
+    if let Some(side) = filters.side {
+        let team_ids = resolved.our_team_ids();
+        if team_ids.is_empty() {
+            sql.push("side", "0", Vec::new());
+        } else {
+            let (condition, params) = side_condition(side, &team_ids);
+            sql.push("side", condition, params);
+        }
+    }
+
+    if !filters.tags.is_empty() {
+        let (condition, params) = tags_condition(&filters.tags);
+        sql.push("tags", condition, params);
+    }
+    if let Some(reviewed) = filters.reviewed {
+        sql.push("reviewed", reviewed_condition(reviewed), []);
+    }
+
+    if !filters.champions_picked.is_empty() {
+        let our_teams = resolved.our_team_ids();
+        // Like side: with no team known, no champion was picked by us or against us.
+        if our_teams.is_empty() && filters.champions_picked.iter().any(|s| s.team.is_some()) {
+            sql.push("picked_by_team", "0", Vec::new());
+        }
+        let matches = filters
+            .champions_picked
+            .iter()
+            .map(|selection| participant_match(selection, &our_teams))
+            .collect();
+        push_by_mode(
+            &mut sql,
+            "champions_picked",
+            &filters.champ_picked_mode,
+            matches,
+            picked,
+            None,
+        );
+    }
+
+    for matchup in &filters.matchups {
+        if let Some((condition, params)) = matchup_match(matchup, &resolved.our_team_ids()) {
+            sql.push("matchups", condition, params);
+        }
+    }
+
+    if !filters.champions_banned.is_empty() {
+        let our_teams = resolved.our_team_ids();
+        if our_teams.is_empty() && filters.champions_banned.iter().any(|s| s.team.is_some()) {
+            sql.push("banned_by_team", "0", Vec::new());
+        }
+        let matches = filters
+            .champions_banned
+            .iter()
+            .map(|selection| ban_match(selection, &our_teams))
+            .collect();
+        // Series without a parsed draft can't be ruled out, so they are kept.
+        push_by_mode(
+            &mut sql,
+            "champions_banned",
+            &filters.champ_banned_mode,
+            matches,
+            banned,
+            Some(NO_DRAFT),
+        );
+    }
+
+    if !filters.players.is_empty() {
+        let names: Vec<String> = filters.players.iter().map(|p| p.value.clone()).collect();
+        let (condition, params) = player_condition(&resolved.player_refs, &names);
+        sql.push("players", condition, params);
+    }
+
+    sql
+}
+
+#[derive(QueryableByName)]
+struct SeriesIdRow {
+    #[diesel(sql_type = Text)]
+    series_id: String,
+}
+
+/// Ids of the series matching `filter`, newest first.
+pub fn matching_series_ids(
+    connection: &mut SqliteConnection,
+    filter: &SqlFilter,
+) -> QueryResult<Vec<String>> {
+    let query = diesel::sql_query(format!(
+        "SELECT series.series_id FROM series
+         WHERE {}
+         ORDER BY series.start_time_scheduled IS NULL, series.start_time_scheduled DESC",
+        filter.where_clause()
+    ))
+    .into_boxed();
+    filter
+        .bind(query)
+        .load::<SeriesIdRow>(connection)
+        .map(|rows| rows.into_iter().map(|row| row.series_id).collect())
+}
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+fn count_matching(connection: &mut SqliteConnection, conditions: &[Condition]) -> QueryResult<i64> {
+    let query = diesel::sql_query(format!(
+        "SELECT COUNT(*) AS count FROM series WHERE {}",
+        where_clause(conditions)
+    ))
+    .into_boxed();
+    bind_values(
+        query,
+        conditions.iter().flat_map(|condition| &condition.params),
+    )
+    .get_result::<CountRow>(connection)
+    .map(|row| row.count)
+}
+
+/// Number of series matching `filter`.
+pub fn count_series(connection: &mut SqliteConnection, filter: &SqlFilter) -> QueryResult<i64> {
+    count_matching(connection, &filter.conditions)
+}
+
+#[derive(Debug, Serialize)]
+pub struct StageDiagnostic {
+    pub stage: &'static str, // e.g. "patch", "champions_picked"
+    pub excluded: i64,       // series this stage removed from what the earlier stages left
+    pub remaining: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FilterDiagnostics {
+    pub total: i64, // series in the database before filtering
+    pub stages: Vec<StageDiagnostic>,
+    // Why my team is unknown, when the filters needed it; their team stages then use none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub my_team_error: Option<String>,
+}
+
+/// Applies the filter stages one at a time, in the order the sidebar lists them, and
+/// reports how many series each one excluded.
+pub fn diagnose(
+    connection: &mut SqliteConnection,
+    filter: &SqlFilter,
+) -> QueryResult<FilterDiagnostics> {
+    let total = count_matching(connection, &[])?;
+    let mut stages = Vec::new();
+    let mut remaining = total;
+    let mut end = 0;
+    while end < filter.conditions.len() {
+        let stage = filter.conditions[end].stage;
+        while end < filter.conditions.len() && filter.conditions[end].stage == stage {
+            end += 1;
+        }
+        let after = count_matching(connection, &filter.conditions[..end])?;
+        stages.push(StageDiagnostic {
+            stage,
+            excluded: remaining - after,
+            remaining: after,
+        });
+        remaining = after;
+    }
+    Ok(FilterDiagnostics {
+        total,
+        stages,
+        my_team_error: None,
+    })
+}
@@ -0,0 +1,214 @@
+// src-tauri/filters/page.rs
+use super::{bind_values, placeholders, SqlFilter, SqlValue};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Date,
+    Duration, // total game time across the series
+    Opponent, // the other team's name, relative to my teams
+    Result,   // my score minus the opponent's
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder {
+            key: SortKey::Date,
+            descending: true,
+        }
+    }
+}
+
+impl SortKey {
+    fn is_numeric(self) -> bool {
+        matches!(self, SortKey::Duration | SortKey::Result)
+    }
+
+    // SQL for the sort value of a series, and its parameters. Series without a value
+    // (no start time, no durations, not involving my team) sort last.
+    fn expression(self, my_team_ids: &[String]) -> (String, Vec<SqlValue>) {
+        let known = !my_team_ids.is_empty();
+        let teams = placeholders(my_team_ids.len());
+        let my_teams = |count: usize| {
+            (0..count)
+                .flat_map(|_| my_team_ids.iter().map(|id| SqlValue::Text(id.clone())))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            SortKey::Date => ("series.start_time_scheduled".to_string(), Vec::new()),
+            SortKey::Duration => (
+                "(SELECT SUM(g.duration_seconds) FROM games g WHERE g.series_id = series.series_id)"
+                    .to_string(),
+                Vec::new(),
+            ),
+            SortKey::Opponent if known => (
+                format!(
+                    "CASE WHEN series.team1_id IN ({teams}) THEN series.team2_name
+                          WHEN series.team2_id IN ({teams}) THEN series.team1_name
+                          ELSE series.team2_name END"
+                ),
+                my_teams(2),
+            ),
+            SortKey::Opponent => ("series.team2_name".to_string(), Vec::new()),
+            SortKey::Result if known => (
+                format!(
+                    "CASE WHEN series.team1_id IN ({teams}) THEN series.team1_score - series.team2_score
+                          WHEN series.team2_id IN ({teams}) THEN series.team2_score - series.team1_score END"
+                ),
+                my_teams(2),
+            ),
+            SortKey::Result => ("NULL".to_string(), Vec::new()),
+        }
+    }
+}
+
+/// Where the previous page ended. Sent to the UI as an opaque string.
+#[derive(Debug, Serialize, Deserialize)]
+struct PageCursor {
+    sort: SortKey,
+    descending: bool,
+    value: Option<String>, // sort value of the last row, as text
+    series_id: String,
+}
+
+impl PageCursor {
+    fn decode(cursor: &str, order: SortOrder) -> Result<Self, String> {
+        let cursor: PageCursor = URL_SAFE_NO_PAD
+            .decode(cursor.trim())
+            .map_err(|err| err.to_string())
+            .and_then(|json| serde_json::from_slice(&json).map_err(|err| err.to_string()))
+            .map_err(|err| format!("Invalid page cursor: {}", err))?;
+        if cursor.sort != order.key || cursor.descending != order.descending {
+            return Err("Page cursor belongs to a different sort order".to_string());
+        }
+        Ok(cursor)
+    }
+
+    fn encode(&self) -> String {
+        serde_json::to_vec(self)
+            .map(|json| URL_SAFE_NO_PAD.encode(json))
+            .unwrap_or_default()
+    }
+
+    // Rows strictly after this cursor, with NULL sort values last in either direction.
+    fn condition(&self, numeric: bool) -> (String, Vec<SqlValue>) {
+        let cmp = if self.descending { "<" } else { ">" };
+        let series_id = SqlValue::Text(self.series_id.clone());
+        match self.value {
+            None => (
+                format!("(sort_key IS NULL AND series_id {cmp} ?)"),
+                vec![series_id],
+            ),
+            Some(ref value) => {
+                let value = || match value.parse::<i32>() {
+                    Ok(number) if numeric => SqlValue::Integer(number),
+                    _ => SqlValue::Text(value.clone()),
+                };
+                (
+                    format!(
+                        "(sort_key IS NULL OR sort_key {cmp} ? OR (sort_key = ? AND series_id {cmp} ?))"
+                    ),
+                    vec![value(), value(), series_id],
+                )
+            }
+        }
+    }
+}
+
+#[derive(QueryableByName)]
+struct PageRow {
+    #[diesel(sql_type = Text)]
+    series_id: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    sort_value: Option<String>,
+}
+
+pub struct Page {
+    pub series_ids: Vec<String>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
+}
+
+/// One page of series ids matching `filter`, in `order`, starting after `cursor`.
+pub fn load_page(
+    connection: &mut SqliteConnection,
+    filter: &SqlFilter,
+    order: SortOrder,
+    my_team_ids: &[String],
+    cursor: Option<&str>,
+    page_size: usize,
+) -> Result<Page, String> {
+    let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+    let cursor = cursor
+        .filter(|cursor| !cursor.is_empty())
+        .map(|cursor| PageCursor::decode(cursor, order))
+        .transpose()?;
+
+    let (sort_expression, sort_params) = order.key.expression(my_team_ids);
+    let (after, after_params) = match cursor {
+        Some(ref cursor) => cursor.condition(order.key.is_numeric()),
+        None => ("1 = 1".to_string(), Vec::new()),
+    };
+    let direction = if order.descending { "DESC" } else { "ASC" };
+    let query = diesel::sql_query(format!(
+        "SELECT series_id, CAST(sort_key AS TEXT) AS sort_value FROM (
+             SELECT series.series_id, {sort_expression} AS sort_key
+             FROM series
+             WHERE {}
+         )
+         WHERE {after}
+         ORDER BY sort_key IS NULL, sort_key {direction}, series_id {direction}
+         LIMIT ?",
+        filter.where_clause()
+    ))
+    .into_boxed();
+    let limit = SqlValue::Integer(page_size as i32 + 1);
+    let mut rows = bind_values(
+        query,
+        sort_params
+            .iter()
+            .chain(filter.params())
+            .chain(&after_params)
+            .chain([&limit]),
+    )
+    .load::<PageRow>(connection)
+    .map_err(|err| format!("Error querying series: {}", err))?;
+
+    // One extra row tells us whether another page follows.
+    let has_more = rows.len() > page_size;
+    rows.truncate(page_size);
+    let next_cursor = rows.last().filter(|_| has_more).map(|row| {
+        PageCursor {
+            sort: order.key,
+            descending: order.descending,
+            value: row.sort_value.clone(),
+            series_id: row.series_id.clone(),
+        }
+        .encode()
+    });
+
+    let total = super::count_series(connection, filter)
+        .map_err(|err| format!("Error counting series: {}", err))?;
+
+    Ok(Page {
+        series_ids: rows.into_iter().map(|row| row.series_id).collect(),
+        next_cursor,
+        total,
+    })
+}
@@ -0,0 +1,830 @@
+// src-tauri/filters/query.rs
+use super::{
+    ban_match, banned, date_condition, game_result_condition, participant_match, patch_condition,
+    picked, player_condition, resolve_player_refs, resolve_team_grid_ids, result_condition,
+    reviewed_condition, side_condition, tags_condition, team_condition, weekday_condition, Outcome,
+    SqlValue, COMPLETE_RESULT,
+};
+use crate::commands::{ChampionSelection, ResultScope, Role, Side, TeamRelation};
+use crate::patch::Patch;
+use crate::timestamp::{DateBound, Timezone};
+use chrono::{NaiveDate, Weekday};
+use diesel::prelude::*;
+use std::fmt;
+
+// Grammar, keywords case-insensitive:
+//
+//   expr      := and ("OR" and)*
+//   and       := unary ("AND" unary)*
+//   unary     := "NOT" unary | "(" expr ")" | predicate
+//   predicate := ("won" | "lost") ["game"] | "drew" | "reviewed"
+//              | ("patch" | "date" | "weekday" | "team" | "player" | "tag" | "side") op value
+//              | champion ("OR" champion)* [("picked" | "banned") qualifier*]
+//   qualifier := "by" ("us" | "them") | "as" role | "in" "phase" (1 | 2)
+//   op        := "=" | "!=" | "<" | "<=" | ">" | ">="
+//
+// Names with spaces are double quoted. A bare champion means picked by either team, and
+// qualifiers after a list of champions apply to each of them, so
+// `(Azir OR Orianna picked by us) AND NOT lost AND patch >= 14.10` reads as written. A list
+// without qualifiers is plain ORs: `Azir OR Orianna AND won` is `Azir OR (Orianna AND won)`.
+// `won` is a series won on games, `won game` a series with a game won. Dates and weekdays
+// are read in the caller's timezone.
+
+const KEYWORDS: &[&str] = &[
+    "and", "or", "not", "won", "lost", "drew", "game", "reviewed", "patch", "date", "weekday",
+    "team", "player", "tag", "side", "picked", "banned", "by", "as", "in", "phase",
+];
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub column: usize, // 1-based, in characters
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (column {})", self.message, self.column)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn sql(self) -> &'static str {
+        match self {
+            CmpOp::Eq => "=",
+            CmpOp::Ne => "<>",
+            CmpOp::Lt => "<",
+            CmpOp::Le => "<=",
+            CmpOp::Gt => ">",
+            CmpOp::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Op(CmpOp),
+    Word(String),   // keyword, name or value
+    Quoted(String), // always a name or value
+}
+
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    column: usize,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Spanned>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let column = i + 1;
+        let c = chars[i];
+        let token = match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => {
+                i += 1;
+                Token::LParen
+            }
+            ')' => {
+                i += 1;
+                Token::RParen
+            }
+            '=' | '!' | '<' | '>' => {
+                let next = chars.get(i + 1).copied();
+                let (op, len) = match (c, next) {
+                    ('=', _) => (CmpOp::Eq, 1),
+                    ('!', Some('=')) => (CmpOp::Ne, 2),
+                    ('<', Some('=')) => (CmpOp::Le, 2),
+                    ('<', Some('>')) => (CmpOp::Ne, 2),
+                    ('<', _) => (CmpOp::Lt, 1),
+                    ('>', Some('=')) => (CmpOp::Ge, 2),
+                    ('>', _) => (CmpOp::Gt, 1),
+                    _ => {
+                        return Err(ParseError {
+                            message: "Expected '!=' after '!'".to_string(),
+                            column,
+                        })
+                    }
+                };
+                i += len;
+                Token::Op(op)
+            }
+            '"' => {
+                let start = i + 1;
+                let end = chars[start..]
+                    .iter()
+                    .position(|&c| c == '"')
+                    .map(|offset| start + offset)
+                    .ok_or(ParseError {
+                        message: "Unterminated quoted name".to_string(),
+                        column,
+                    })?;
+                i = end + 1;
+                Token::Quoted(chars[start..end].iter().collect())
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !matches!(chars[i], '(' | ')' | '"' | '=' | '!' | '<' | '>')
+                {
+                    i += 1;
+                }
+                Token::Word(chars[start..i].iter().collect())
+            }
+        };
+        tokens.push(Spanned { token, column });
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Predicate(Predicate),
+}
+
+#[derive(Debug)]
+pub enum Predicate {
+    Result(Outcome, ResultScope),
+    Reviewed,
+    Patch(CmpOp, Patch),
+    Date(CmpOp, NaiveDate),
+    Weekday(Weekday),
+    Team(String),
+    Player(String),
+    Tag(String),
+    Side(Side), // a game my team played on this side
+    Picked {
+        champion: String,
+        by: Option<TeamRelation>,
+        role: Option<Role>,
+    },
+    Banned {
+        champion: String,
+        by: Option<TeamRelation>,
+        phase: Option<i32>,
+    },
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    position: usize,
+    end_column: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|spanned| &spanned.token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|spanned| &spanned.token)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end_column, |spanned| spanned.column)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            message: message.into(),
+            column: self.column(),
+        })
+    }
+
+    fn describe(&self) -> String {
+        match self.peek() {
+            None => "end of query".to_string(),
+            Some(Token::LParen) => "'('".to_string(),
+            Some(Token::RParen) => "')'".to_string(),
+            Some(Token::Op(op)) => format!("'{}'", op.sql()),
+            Some(Token::Word(word)) | Some(Token::Quoted(word)) => format!("'{}'", word),
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str, context: &str) -> Result<(), ParseError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            self.error(format!(
+                "Expected '{}' {}, found {}",
+                keyword,
+                context,
+                self.describe()
+            ))
+        }
+    }
+
+    // A name or value: a quoted string or any word that isn't a keyword.
+    fn is_value(token: Option<&Token>) -> bool {
+        match token {
+            Some(Token::Quoted(_)) => true,
+            Some(Token::Word(word)) => !KEYWORDS.contains(&word.to_lowercase().as_str()),
+            _ => false,
+        }
+    }
+
+    fn value(&mut self, what: &str) -> Result<String, ParseError> {
+        if !Self::is_value(self.peek()) {
+            return self.error(format!("Expected {}, found {}", what, self.describe()));
+        }
+        match self.next() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    fn op(&mut self, field: &str) -> Result<CmpOp, ParseError> {
+        match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.position += 1;
+                Ok(op)
+            }
+            _ => self.error(format!(
+                "Expected a comparison after '{}', found {}",
+                field,
+                self.describe()
+            )),
+        }
+    }
+
+    // Fields that only make sense as equal / not equal.
+    fn equality(&mut self, field: &str) -> Result<bool, ParseError> {
+        let column = self.column();
+        match self.op(field)? {
+            CmpOp::Eq => Ok(true),
+            CmpOp::Ne => Ok(false),
+            op => Err(ParseError {
+                message: format!("'{}' can't be compared with '{}'", field, op.sql()),
+                column,
+            }),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.and()?;
+        while self.eat_keyword("or") {
+            let right = self.and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.unary()?;
+        while self.eat_keyword("and") {
+            let right = self.unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some(&Token::LParen) {
+            let open = self.column();
+            self.position += 1;
+            let inner = self.expr()?;
+            if self.next() != Some(Token::RParen) {
+                self.position -= 1;
+                return self.error(format!(
+                    "Expected ')' to close the '(' at column {}, found {}",
+                    open,
+                    self.describe()
+                ));
+            }
+            return Ok(inner);
+        }
+        self.predicate()
+    }
+
+    fn predicate(&mut self) -> Result<Expr, ParseError> {
+        let column = self.column();
+        let keyword = match self.peek() {
+            _ if Self::is_value(self.peek()) => String::new(),
+            Some(Token::Word(word)) => word.to_lowercase(),
+            _ => {
+                return self.error(format!(
+                    "Expected a filter such as 'won', 'patch >= 14.10' or a champion, found {}",
+                    self.describe()
+                ))
+            }
+        };
+        let predicate = |predicate| Ok(Expr::Predicate(predicate));
+        let negated = |expr: Expr, equal: bool| {
+            if equal {
+                Ok(expr)
+            } else {
+                Ok(Expr::Not(Box::new(expr)))
+            }
+        };
+        match keyword.as_str() {
+            "won" | "lost" | "drew" => {
+                self.position += 1;
+                let outcome = match keyword.as_str() {
+                    "won" => Outcome::Won,
+                    "lost" => Outcome::Lost,
+                    _ => Outcome::Drawn,
+                };
+                let scope_column = self.column();
+                if !self.eat_keyword("game") {
+                    return predicate(Predicate::Result(outcome, ResultScope::Series));
+                }
+                if outcome == Outcome::Drawn {
+                    return Err(ParseError {
+                        message: "Games can't be drawn; use 'drew' for series".to_string(),
+                        column: scope_column,
+                    });
+                }
+                predicate(Predicate::Result(outcome, ResultScope::Game))
+            }
+            "reviewed" => {
+                self.position += 1;
+                predicate(Predicate::Reviewed)
+            }
+            "patch" => {
+                self.position += 1;
+                let op = self.op("patch")?;
+                let value_column = self.column();
+                let patch = self
+                    .value("a patch such as 14.10")?
+                    .parse::<Patch>()
+                    .map_err(|message| ParseError {
+                        message,
+                        column: value_column,
+                    })?;
+                predicate(Predicate::Patch(op, patch))
+            }
+            "date" => {
+                self.position += 1;
+                let op = self.op("date")?;
+                let value_column = self.column();
+                let date = self.value("a date such as 2024-05-01")?;
+                let date =
+                    NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|_| ParseError {
+                        message: format!("'{}' is not a date such as 2024-05-01", date),
+                        column: value_column,
+                    })?;
+                predicate(Predicate::Date(op, date))
+            }
+            "weekday" => {
+                self.position += 1;
+                let equal = self.equality("weekday")?;
+                let value_column = self.column();
+                let day = self.value("a day such as tuesday")?;
+                let day = day.parse::<Weekday>().map_err(|_| ParseError {
+                    message: format!("'{}' is not a day such as tuesday or tue", day),
+                    column: value_column,
+                })?;
+                negated(Expr::Predicate(Predicate::Weekday(day)), equal)
+            }
+            "team" | "player" | "tag" => {
+                self.position += 1;
+                let equal = self.equality(&keyword)?;
+                let name = self.value(&format!("a {} name", keyword))?;
+                let expr = Expr::Predicate(match keyword.as_str() {
+                    "team" => Predicate::Team(name),
+                    "player" => Predicate::Player(name),
+                    _ => Predicate::Tag(name),
+                });
+                negated(expr, equal)
+            }
+            "side" => {
+                self.position += 1;
+                let equal = self.equality("side")?;
+                let value_column = self.column();
+                let side = match self.value("'blue' or 'red'")?.to_lowercase().as_str() {
+                    "blue" => Side::Blue,
+                    "red" => Side::Red,
+                    other => {
+                        return Err(ParseError {
+                            message: format!("Expected 'blue' or 'red', found '{}'", other),
+                            column: value_column,
+                        })
+                    }
+                };
+                negated(Expr::Predicate(Predicate::Side(side)), equal)
+            }
+            "" => self.champions(),
+            _ => Err(ParseError {
+                message: format!("'{}' can't start a filter", keyword),
+                column,
+            }),
+        }
+    }
+
+    // `Azir OR Orianna picked by us`: the qualifiers apply to every champion in the list.
+    fn champions(&mut self) -> Result<Expr, ParseError> {
+        let mut champions = vec![self.value("a champion")?];
+        let after_first = self.position;
+        while self.is_keyword("or") && Self::is_value(self.peek_at(1)) {
+            self.position += 1;
+            champions.push(self.value("a champion")?);
+        }
+        // Without qualifiers the list is plain ORs, so `a OR b AND c` keeps AND binding tighter.
+        let qualified = ["picked", "banned", "by", "as", "in"]
+            .iter()
+            .any(|keyword| self.is_keyword(keyword));
+        if !qualified {
+            champions.truncate(1);
+            self.position = after_first;
+        }
+
+        let banned = if self.eat_keyword("banned") {
+            true
+        } else {
+            self.eat_keyword("picked");
+            false
+        };
+        let (mut by, mut role, mut phase) = (None, None, None);
+        loop {
+            if self.eat_keyword("by") {
+                by = Some(match self.next() {
+                    Some(Token::Word(word)) if word.eq_ignore_ascii_case("us") => TeamRelation::Us,
+                    Some(Token::Word(word))
+                        if word.eq_ignore_ascii_case("them")
+                            || word.eq_ignore_ascii_case("opponent") =>
+                    {
+                        TeamRelation::Opponent
+                    }
+                    _ => {
+                        self.position -= 1;
+                        return self.error(format!(
+                            "Expected 'us' or 'them' after 'by', found {}",
+                            self.describe()
+                        ));
+                    }
+                });
+            } else if !banned && self.eat_keyword("as") {
+                let column = self.column();
+                let name = self.value("a role such as mid or support")?;
+                role = Some(
+                    serde_json::from_value::<Role>(serde_json::Value::String(name.to_lowercase()))
+                        .map_err(|_| ParseError {
+                            message: format!(
+                                "'{}' is not a role (top, jungle, mid, bot or support)",
+                                name
+                            ),
+                            column,
+                        })?,
+                );
+            } else if banned && self.eat_keyword("in") {
+                self.expect_keyword("phase", "after 'in'")?;
+                let column = self.column();
+                phase = match self.value("phase 1 or 2")?.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    other => {
+                        return Err(ParseError {
+                            message: format!("Expected phase 1 or 2, found '{}'", other),
+                            column,
+                        })
+                    }
+                };
+            } else {
+                break;
+            }
+        }
+
+        let mut predicates = champions.into_iter().map(|champion| {
+            Expr::Predicate(if banned {
+                Predicate::Banned {
+                    champion,
+                    by,
+                    phase,
+                }
+            } else {
+                Predicate::Picked { champion, by, role }
+            })
+        });
+        let first = predicates.next().expect("at least one champion");
+        Ok(predicates.fold(first, |left, right| {
+            Expr::Or(Box::new(left), Box::new(right))
+        }))
+    }
+}
+
+/// Parses a query such as `(Azir OR Orianna picked by us) AND NOT lost AND patch >= 14.10`.
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(ParseError {
+            message: "The query is empty".to_string(),
+            column: 1,
+        });
+    }
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        end_column: input.chars().count() + 1,
+    };
+    let expr = parser.expr()?;
+    if parser.peek().is_some() {
+        return parser.error(format!(
+            "Expected 'AND', 'OR' or the end of the query, found {}",
+            parser.describe()
+        ));
+    }
+    Ok(expr)
+}
+
+impl Expr {
+    /// Whether evaluating the query needs my team (results, sides or "by us/them").
+    pub fn needs_my_team(&self) -> bool {
+        match self {
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                left.needs_my_team() || right.needs_my_team()
+            }
+            Expr::Not(inner) => inner.needs_my_team(),
+            Expr::Predicate(predicate) => matches!(
+                predicate,
+                Predicate::Result(..)
+                    | Predicate::Side(_)
+                    | Predicate::Picked { by: Some(_), .. }
+                    | Predicate::Banned { by: Some(_), .. }
+            ),
+        }
+    }
+
+    /// Compiles the query into one SQL condition on `series`, resolving team and player
+    /// names (including past names) along the way.
+    pub fn compile(
+        &self,
+        connection: &mut SqliteConnection,
+        my_team_ids: &[String],
+        timezone: Timezone,
+    ) -> Result<(String, Vec<SqlValue>), String> {
+        match self {
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                let joiner = if matches!(self, Expr::And(..)) {
+                    "AND"
+                } else {
+                    "OR"
+                };
+                let (left, mut params) = left.compile(connection, my_team_ids, timezone)?;
+                let (right, right_params) = right.compile(connection, my_team_ids, timezone)?;
+                params.extend(right_params);
+                Ok((format!("({left}) {joiner} ({right})"), params))
+            }
+            Expr::Not(inner) => {
+                let (inner, params) = inner.compile(connection, my_team_ids, timezone)?;
+                Ok((format!("NOT ({inner})"), params))
+            }
+            Expr::Predicate(predicate) => predicate.compile(connection, my_team_ids, timezone),
+        }
+    }
+}
+
+impl Predicate {
+    fn compile(
+        &self,
+        connection: &mut SqliteConnection,
+        my_team_ids: &[String],
+        timezone: Timezone,
+    ) -> Result<(String, Vec<SqlValue>), String> {
+        let my_teams = |what: &str| {
+            if my_team_ids.is_empty() {
+                Err(format!(
+                    "'{}' needs your team, which couldn't be determined. Set it in settings.",
+                    what
+                ))
+            } else {
+                Ok(my_team_ids)
+            }
+        };
+        let our_teams = |by: &Option<TeamRelation>| match by {
+            Some(_) => my_teams("by us/them").map(<[String]>::to_vec),
+            None => Ok(Vec::new()),
+        };
+        let lookup_error = |err: diesel::result::Error| format!("Error resolving names: {}", err);
+
+        Ok(match self {
+            Predicate::Result(outcome, scope) => {
+                let what = match outcome {
+                    Outcome::Won => "won",
+                    Outcome::Lost => "lost",
+                    Outcome::Drawn => "drew",
+                };
+                let my_teams = my_teams(what)?;
+                match scope {
+                    ResultScope::Series => {
+                        let (condition, params) = result_condition(my_teams, *outcome);
+                        (format!("{COMPLETE_RESULT} AND ({condition})"), params)
+                    }
+                    ResultScope::Game => game_result_condition(my_teams, *outcome == Outcome::Won),
+                }
+            }
+            Predicate::Reviewed => (reviewed_condition(true), Vec::new()),
+            Predicate::Patch(op, patch) => patch_condition(op.sql(), *patch),
+            Predicate::Date(op, date) => date_condition(op.sql(), DateBound::Day(*date), timezone),
+            Predicate::Weekday(day) => weekday_condition(&[*day], timezone),
+            Predicate::Team(name) => {
+                let names = std::slice::from_ref(name);
+                let team_ids = resolve_team_grid_ids(connection, names).map_err(lookup_error)?;
+                team_condition(&team_ids, names)
+            }
+            Predicate::Player(name) => {
+                let names = std::slice::from_ref(name);
+                let player_refs = resolve_player_refs(connection, names).map_err(lookup_error)?;
+                player_condition(&player_refs, names)
+            }
+            Predicate::Tag(tag) => tags_condition(std::slice::from_ref(tag)),
+            Predicate::Side(side) => side_condition(*side, my_teams("side")?),
+            Predicate::Picked { champion, by, role } => {
+                let (condition, params) =
+                    participant_match(&selection(champion, *by, *role, None), &our_teams(by)?);
+                (picked(&condition), params)
+            }
+            Predicate::Banned {
+                champion,
+                by,
+                phase,
+            } => {
+                let (condition, params) =
+                    ban_match(&selection(champion, *by, None, *phase), &our_teams(by)?);
+                (banned(&condition), params)
+            }
+        })
+    }
+}
+
+fn selection(
+    champion: &str,
+    team: Option<TeamRelation>,
+    role: Option<Role>,
+    phase: Option<i32>,
+) -> ChampionSelection {
+    ChampionSelection {
+        value: champion.to_string(),
+        label: champion.to_string(),
+        champ: champion.to_string(),
+        role,
+        team,
+        phase,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Plain champions by name, other predicates as Debug, every operator parenthesized.
+    fn show(expr: &Expr) -> String {
+        match expr {
+            Expr::And(left, right) => format!("({} AND {})", show(left), show(right)),
+            Expr::Or(left, right) => format!("({} OR {})", show(left), show(right)),
+            Expr::Not(inner) => format!("NOT {}", show(inner)),
+            Expr::Predicate(Predicate::Picked {
+                champion,
+                by: None,
+                role: None,
+            }) => champion.clone(),
+            Expr::Predicate(predicate) => format!("{:?}", predicate),
+        }
+    }
+
+    fn parsed(input: &str) -> String {
+        show(&parse(input).unwrap_or_else(|err| panic!("{}: {}", input, err)))
+    }
+
+    fn error(input: &str) -> (String, usize) {
+        let err = parse(input).expect_err(input);
+        (err.message, err.column)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(parsed("a OR b AND c"), "(a OR (b AND c))");
+        assert_eq!(parsed("a AND b OR c"), "((a AND b) OR c)");
+        assert_eq!(parsed("(a OR b) AND c"), "((a OR b) AND c)");
+        assert_eq!(parsed("NOT a AND b"), "(NOT a AND b)");
+        assert_eq!(parsed("a or b and not c"), "(a OR (b AND NOT c))");
+        assert_eq!(
+            parsed("Azir OR Orianna AND won"),
+            "(Azir OR (Orianna AND Result(Won, Series)))"
+        );
+    }
+
+    #[test]
+    fn qualifiers_apply_to_every_champion_in_a_list() {
+        assert_eq!(
+            parsed("Azir OR Orianna picked by us"),
+            "(Picked { champion: \"Azir\", by: Some(Us), role: None } \
+             OR Picked { champion: \"Orianna\", by: Some(Us), role: None })"
+        );
+        assert_eq!(
+            parsed("Azir OR \"Lee Sin\" banned in phase 2"),
+            "(Banned { champion: \"Azir\", by: None, phase: Some(2) } \
+             OR Banned { champion: \"Lee Sin\", by: None, phase: Some(2) })"
+        );
+        // A keyword after OR ends the list, so `won` isn't a champion and gets no qualifier.
+        assert_eq!(
+            parsed("Azir OR Orianna by us OR won"),
+            "((Picked { champion: \"Azir\", by: Some(Us), role: None } \
+             OR Picked { champion: \"Orianna\", by: Some(Us), role: None }) \
+             OR Result(Won, Series))"
+        );
+        // Parentheses end the list too.
+        assert_eq!(
+            parsed("Azir OR (Orianna by us)"),
+            "(Azir OR Picked { champion: \"Orianna\", by: Some(Us), role: None })"
+        );
+    }
+
+    #[test]
+    fn unterminated_quote() {
+        assert_eq!(
+            error("team = \"T1 Academy"),
+            ("Unterminated quoted name".to_string(), 8)
+        );
+    }
+
+    #[test]
+    fn bang_without_equals() {
+        assert_eq!(error("!"), ("Expected '!=' after '!'".to_string(), 1));
+        assert_eq!(
+            error("side ! blue"),
+            ("Expected '!=' after '!'".to_string(), 6)
+        );
+    }
+
+    #[test]
+    fn unclosed_paren() {
+        assert_eq!(
+            error("(won OR lost"),
+            (
+                "Expected ')' to close the '(' at column 1, found end of query".to_string(),
+                13
+            )
+        );
+        assert_eq!(
+            error("won AND (Azir OR (lost) reviewed"),
+            (
+                "Expected ')' to close the '(' at column 9, found 'reviewed'".to_string(),
+                25
+            )
+        );
+    }
+
+    #[test]
+    fn trailing_tokens() {
+        assert_eq!(
+            error("won lost"),
+            (
+                "Expected 'AND', 'OR' or the end of the query, found 'lost'".to_string(),
+                5
+            )
+        );
+        assert_eq!(
+            error("won)"),
+            (
+                "Expected 'AND', 'OR' or the end of the query, found ')'".to_string(),
+                4
+            )
+        );
+    }
+}
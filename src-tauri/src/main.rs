@@ -2,12 +2,16 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 mod commands;
 mod db;
+mod filters;
+mod patch;
+mod settings;
 mod sync;
+mod timestamp;
 use env_logger::Builder;
-use log::LevelFilter;
+use log::{error, LevelFilter};
 use serde_json::json;
 use std::io::Write;
-use tauri::command;
+use tauri::{command, Manager};
 
 fn main() {
     // Initialize the logger
@@ -26,8 +30,13 @@ fn main() {
         .init();
 
     tauri::Builder::default()
-        .setup(|_app| {
-            db::init();
+        .setup(|app| {
+            // Keep the app running so commands can report the problem to the UI.
+            let pool = db::init();
+            if let Err(ref err) = pool {
+                error!("Failed to initialise database: {}", err);
+            }
+            app.manage(db::Database::new(pool));
             Ok(())
         })
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -38,10 +47,36 @@ fn main() {
             logout,
             commands::get_games,
             commands::start_sync,
+            commands::get_sync_schedule,
+            commands::update_sync_schedule,
+            commands::get_settings,
+            commands::update_settings,
+            commands::get_my_team_candidates,
+            commands::set_my_team,
             commands::get_series_with_participants,
+            commands::list_series,
+            commands::diagnose_filters,
+            commands::query_series,
+            commands::get_series_details,
+            commands::get_draft,
+            commands::get_annotations,
+            commands::save_annotation,
+            commands::delete_annotation,
+            commands::get_annotation_tags,
+            commands::get_filter_presets,
+            commands::get_filter_preset,
+            commands::create_filter_preset,
+            commands::update_filter_preset,
+            commands::delete_filter_preset,
             commands::get_players,
             commands::get_teams,
-            commands::clear_db
+            commands::search,
+            commands::get_player_identities,
+            commands::merge_players,
+            commands::clear_db,
+            commands::purge_series,
+            commands::backup_db,
+            commands::restore_db
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -57,7 +92,10 @@ async fn login(username: String, password: String) -> Result<String, String> {
     // Send the POST request using reqwest
     let client = reqwest::Client::new();
     let response = client
-        .post("https://lol.grid.gg/auth/login")
+        .post(format!(
+            "{}/login",
+            settings::load_or_default().api.auth_url
+        ))
         .json(&raw_data)
         .send()
         .await
@@ -86,7 +124,10 @@ async fn login(username: String, password: String) -> Result<String, String> {
 async fn logout(auth_token: String, refresh_token: String) -> Result<String, String> {
     let client = reqwest::Client::new();
     let response = client
-        .post("https://lol.grid.gg/auth/logout")
+        .post(format!(
+            "{}/logout",
+            settings::load_or_default().api.auth_url
+        ))
         .header(
             "Cookie",
             format!(
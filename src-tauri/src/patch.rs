@@ -0,0 +1,120 @@
+// src-tauri/patch.rs
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A League patch, e.g. 14.12. Game versions such as "14.12.601.1234" normalize to their
+/// patch, and patches order numerically, so 14.9 < 14.10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Patch {
+    pub major: i32,
+    pub minor: i32,
+}
+
+impl Patch {
+    /// The patch of a game version, or None for placeholders like "None" and "latest".
+    pub fn parse(version: &str) -> Option<Patch> {
+        let mut parts = version.trim().split('.').map(|part| {
+            if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
+                part.parse::<i32>().ok()
+            } else {
+                None
+            }
+        });
+        Some(Patch {
+            major: parts.next()??,
+            minor: parts.next()??,
+        })
+    }
+
+    /// The major of a major-only patch such as "14", which stands for every 14.x patch.
+    pub fn parse_major(value: &str) -> Option<i32> {
+        let value = value.trim();
+        if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+            value.parse().ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for Patch {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Patch::parse(value).ok_or_else(|| format!("'{}' is not a patch such as 14.10", value))
+    }
+}
+
+// Sent to and from the UI as "14.12".
+impl Serialize for Patch {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Patch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patch(major: i32, minor: i32) -> Patch {
+        Patch { major, minor }
+    }
+
+    #[test]
+    fn game_versions_normalize_to_their_patch() {
+        assert_eq!(Patch::parse("14.12"), Some(patch(14, 12)));
+        assert_eq!(Patch::parse("14.12.601.1234"), Some(patch(14, 12)));
+        assert_eq!(Patch::parse(" 14.9 "), Some(patch(14, 9)));
+        assert_eq!(Patch::parse("14.09"), Some(patch(14, 9)));
+        assert_eq!(
+            Patch::parse("14.10").map(|p| p.to_string()),
+            Some("14.10".to_string())
+        );
+    }
+
+    #[test]
+    fn placeholders_and_partial_versions_are_not_patches() {
+        for value in [
+            "", "None", "latest", "14", "14.", ".12", "14.x", "v14.12", "-14.1",
+        ] {
+            assert_eq!(Patch::parse(value), None, "{:?}", value);
+        }
+        assert!("latest".parse::<Patch>().is_err());
+    }
+
+    #[test]
+    fn major_only_patches() {
+        assert_eq!(Patch::parse_major("14"), Some(14));
+        assert_eq!(Patch::parse_major(" 9 "), Some(9));
+        assert_eq!(Patch::parse_major("14.1"), None);
+        assert_eq!(Patch::parse_major(""), None);
+    }
+
+    #[test]
+    fn patches_order_numerically() {
+        assert!(patch(14, 9) < patch(14, 10));
+        assert!(patch(13, 24) < patch(14, 1));
+        assert!(Patch::parse("14.9.600.1") < Patch::parse("14.10.580.2"));
+        let mut patches = vec![patch(14, 10), patch(14, 2), patch(13, 24), patch(14, 9)];
+        patches.sort();
+        assert_eq!(
+            patches,
+            vec![patch(13, 24), patch(14, 2), patch(14, 9), patch(14, 10)]
+        );
+    }
+}
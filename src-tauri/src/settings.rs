@@ -0,0 +1,233 @@
+// src-tauri/settings.rs
+use crate::db::purge::RetentionPolicy;
+use crate::sync::schedule::SyncSchedule;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const SETTINGS_VERSION: u32 = 1;
+const APP_DIR_NAME: &str = "LOLScrimExporter";
+const SETTINGS_FILE_NAME: &str = "settings.json";
+const SERIES_TYPES: [&str; 4] = ["SCRIM", "ESPORTS", "COMPETITIVE", "LOOPFEED"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiEndpoints {
+    pub auth_url: String,          // login/logout live under this
+    pub organisation_url: String,  // "my organisation" lookup
+    pub central_data_url: String,  // series listing and team search
+    pub series_state_url: String,  // live-data-feed series state (scores)
+    pub event_log_url: String,     // event explorer (draft events)
+    pub file_download_url: String, // end-state game summaries
+}
+
+impl Default for ApiEndpoints {
+    fn default() -> Self {
+        ApiEndpoints {
+            auth_url: "https://lol.grid.gg/auth".to_string(),
+            organisation_url: "https://lol.grid.gg/api/organisations/mine".to_string(),
+            central_data_url: "https://api.grid.gg/central-data/graphql".to_string(),
+            series_state_url: "https://api.grid.gg/live-data-feed/series-state/graphql".to_string(),
+            event_log_url: "https://lol.grid.gg/api/event-explorer-api/events/graphql".to_string(),
+            file_download_url: "https://api.grid.gg/file-download".to_string(),
+        }
+    }
+}
+
+/// The GRID teams that are "us". Resolved from the organisation once, or picked by the
+/// user when several teams match, then kept so filters work offline.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MyTeam {
+    pub team_ids: Vec<String>, // Orgs may scrim under several GRID teams; empty until resolved
+    pub org_name: Option<String>, // The organisation name the ids were resolved from
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub database_path: Option<String>, // None uses the default location
+    pub sync: SyncSchedule,
+    pub series_types: Vec<String>, // GRID series types to sync, e.g. ["SCRIM"]
+    pub api: ApiEndpoints,
+    pub my_team: MyTeam,
+    pub retention: RetentionPolicy, // Applied after every sync; keeps everything by default
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            database_path: None,
+            sync: SyncSchedule::default(),
+            series_types: vec!["SCRIM".to_string()],
+            api: ApiEndpoints::default(),
+            my_team: MyTeam::default(),
+            retention: RetentionPolicy::default(),
+        }
+    }
+}
+
+fn validate_url(name: &str, url: &str) -> Result<(), String> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(format!("{} must be an http(s) URL, got '{}'", name, url))
+    }
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != SETTINGS_VERSION {
+            return Err(format!(
+                "Unsupported settings version {} (expected {})",
+                self.version, SETTINGS_VERSION
+            ));
+        }
+        if let Some(ref path) = self.database_path {
+            if !Path::new(path).is_absolute() {
+                return Err(format!("Database path '{}' must be absolute", path));
+            }
+        }
+        self.sync.validate()?;
+        if self.series_types.is_empty() {
+            return Err("At least one series type must be selected".to_string());
+        }
+        for series_type in &self.series_types {
+            if !SERIES_TYPES.contains(&series_type.as_str()) {
+                return Err(format!(
+                    "Unknown series type '{}' (expected one of {:?})",
+                    series_type, SERIES_TYPES
+                ));
+            }
+        }
+        validate_url("auth_url", &self.api.auth_url)?;
+        validate_url("organisation_url", &self.api.organisation_url)?;
+        validate_url("central_data_url", &self.api.central_data_url)?;
+        validate_url("series_state_url", &self.api.series_state_url)?;
+        validate_url("event_log_url", &self.api.event_log_url)?;
+        validate_url("file_download_url", &self.api.file_download_url)?;
+        if self
+            .my_team
+            .team_ids
+            .iter()
+            .any(|team_id| team_id.trim().is_empty())
+        {
+            return Err("My team ids cannot be blank".to_string());
+        }
+        self.retention.validate()
+    }
+}
+
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+}
+
+fn settings_path() -> PathBuf {
+    config_dir().join(SETTINGS_FILE_NAME)
+}
+
+// Upgrades a settings object one version at a time until it reaches SETTINGS_VERSION. Files
+// from before the version field have the v1 shape, so reaching v1 only stamps the version.
+fn migrate(mut value: Value) -> Result<Value, String> {
+    let mut version = match value.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format!("Settings version {} is not a number", version))?,
+    };
+    if version > SETTINGS_VERSION as u64 {
+        return Err(format!(
+            "Settings version {} is newer than this app supports ({})",
+            version, SETTINGS_VERSION
+        ));
+    }
+    while version < SETTINGS_VERSION as u64 {
+        // Steps that change the shape go here, matched on the version being upgraded from.
+        version += 1;
+        value["version"] = Value::from(version);
+    }
+    Ok(value)
+}
+
+/// The saved settings, or the defaults when there is no settings file yet. A file that
+/// can't be read, parsed or validated is an error, so callers that save never overwrite it.
+pub fn load() -> Result<Settings, String> {
+    let path = settings_path();
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Settings::default()),
+        Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err)),
+    };
+    let raw = match serde_json::from_str::<Value>(&contents) {
+        Ok(value) if value.is_object() => value,
+        Ok(_) => return Err(format!("Failed to parse {}: not an object", path.display())),
+        Err(err) => return Err(format!("Failed to parse {}: {}", path.display(), err)),
+    };
+    let migrated =
+        migrate(raw).map_err(|err| format!("Invalid settings in {}: {}", path.display(), err))?;
+    let settings = serde_json::from_value::<Settings>(migrated)
+        .map_err(|err| format!("Invalid settings in {}: {}", path.display(), err))?;
+    settings
+        .validate()
+        .map_err(|err| format!("Invalid settings in {}: {}", path.display(), err))?;
+    Ok(settings)
+}
+
+/// Settings for reading only, falling back to the defaults when the file is broken. Never
+/// save what this returns; use `load` to change settings.
+pub fn load_or_default() -> Settings {
+    load().unwrap_or_else(|err| {
+        warn!("{}, using defaults", err);
+        Settings::default()
+    })
+}
+
+pub fn save(settings: &Settings) -> Result<(), String> {
+    settings.validate()?;
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+    }
+    let contents = serde_json::to_string_pretty(settings).map_err(|err| err.to_string())?;
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+    info!("Saved settings to {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unversioned_settings_are_stamped_v1() {
+        let migrated = migrate(json!({ "series_types": ["ESPORTS"] })).unwrap();
+        assert_eq!(migrated["version"], json!(1));
+        assert_eq!(migrated["series_types"], json!(["ESPORTS"]));
+        let settings: Settings = serde_json::from_value(migrated).unwrap();
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.series_types, vec!["ESPORTS".to_string()]);
+    }
+
+    #[test]
+    fn current_settings_are_unchanged() {
+        let current = serde_json::to_value(Settings::default()).unwrap();
+        assert_eq!(migrate(current.clone()).unwrap(), current);
+    }
+
+    #[test]
+    fn newer_or_malformed_versions_are_errors() {
+        assert!(migrate(json!({ "version": SETTINGS_VERSION + 1 })).is_err());
+        assert!(migrate(json!({ "version": "1" })).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+// src-tauri/sync/draft.rs
+use crate::db::models::NewDraftAction;
+use crate::db::schema::draft_actions::dsl as d;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use log::{info, warn};
+use serde_json::Value;
+
+const PICKS_PER_GAME: usize = 10;
+const FIRST_PHASE_BANS: usize = 6;
+const FIRST_PHASE_PICKS: usize = 6;
+
+// Sets each draft team's side from the teamId (100 blue, 200 red) of a participant playing
+// one of its picks, or the opposite of the other team's when none of its picks match.
+// Champion names are compared without case, spaces or punctuation, as the draft and the
+// summary spell some differently. Games without participants keep the guess.
+const SIDES_FROM_PARTICIPANTS: &str = "
+    WITH pick_sides AS (
+        SELECT DISTINCT pick.game, pick.team_name,
+            CASE p.team_id WHEN 100 THEN 'blue' ELSE 'red' END AS side
+        FROM draft_actions pick
+        JOIN participants p ON p.series_id = pick.series_id AND p.game = pick.game
+        WHERE pick.series_id = ? AND pick.action = 'pick' AND p.team_id IN (100, 200)
+          AND LOWER(REPLACE(REPLACE(REPLACE(p.champion_name, ' ', ''), '''', ''), '.', ''))
+            = LOWER(REPLACE(REPLACE(REPLACE(pick.champion_name, ' ', ''), '''', ''), '.', ''))
+    )
+    UPDATE draft_actions SET side = COALESCE(
+        (SELECT ps.side FROM pick_sides ps
+         WHERE ps.game = draft_actions.game AND ps.team_name = draft_actions.team_name),
+        (SELECT CASE ps.side WHEN 'blue' THEN 'red' ELSE 'blue' END FROM pick_sides ps
+         WHERE ps.game = draft_actions.game AND ps.team_name <> draft_actions.team_name),
+        side
+    )
+    WHERE series_id = ?";
+
+#[derive(Default)]
+struct GameDraft {
+    blue_team: Option<String>,
+    red_team: Option<String>,
+    bans: usize,
+    picks: usize,
+    sequence: i32,
+}
+
+/// Turns a stored event log (the `edges` array from the event explorer) into ordered
+/// pick/ban rows. Each game's draft follows the previous one in the log, so a new game
+/// starts once ten champions have been picked.
+pub fn parse_draft(series_id: &str, event_log: &str) -> Vec<NewDraftAction> {
+    let events = match serde_json::from_str::<Value>(event_log) {
+        Ok(Value::Array(events)) => events,
+        _ => {
+            warn!("Event log for series {} is not an array", series_id);
+            return Vec::new();
+        }
+    };
+
+    // Only trust events after GRID validated the series, like the draft view always has.
+    let validated_index = events
+        .iter()
+        .position(|event| event["node"]["type"].as_str() == Some("grid-validated-series"));
+    let events = match validated_index {
+        Some(index) => &events[index + 1..],
+        None => &events[..],
+    };
+
+    let mut actions = Vec::new();
+    let mut game = 1;
+    let mut draft = GameDraft::default();
+
+    for event in events {
+        let node = &event["node"];
+        let action = match node["type"].as_str() {
+            Some("team-banned-character") => "ban",
+            Some("team-picked-character") => "pick",
+            _ => continue,
+        };
+        let chunks: Vec<&str> = node["sentenceChunks"]
+            .as_array()
+            .map(|chunks| {
+                chunks
+                    .iter()
+                    .map(|chunk| chunk["text"].as_str().unwrap_or_default())
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Expect [team, "banned"/"picked", champion]
+        if chunks.len() < 3 {
+            continue;
+        }
+        let (team, champion) = (chunks[0], chunks[2]);
+
+        if draft.picks >= PICKS_PER_GAME {
+            game += 1;
+            draft = GameDraft::default();
+        }
+
+        // The first team to act in a game is on blue side. A guess: assign_sides corrects it
+        // from the teamId of the participants playing each team's picks.
+        let side = if draft.blue_team.is_none() || draft.blue_team.as_deref() == Some(team) {
+            draft.blue_team.get_or_insert_with(|| team.to_string());
+            "blue"
+        } else {
+            draft.red_team.get_or_insert_with(|| team.to_string());
+            "red"
+        };
+
+        let phase = if action == "ban" {
+            draft.bans += 1;
+            if draft.bans <= FIRST_PHASE_BANS {
+                1
+            } else {
+                2
+            }
+        } else {
+            draft.picks += 1;
+            if draft.picks <= FIRST_PHASE_PICKS {
+                1
+            } else {
+                2
+            }
+        };
+        draft.sequence += 1;
+
+        actions.push(NewDraftAction {
+            series_id: series_id.to_string(),
+            game,
+            sequence: draft.sequence,
+            phase,
+            side: side.to_string(),
+            team_name: team.to_string(),
+            action: action.to_string(),
+            champion_name: champion.to_string(),
+        });
+    }
+    actions
+}
+
+/// Replaces the stored draft for a series with the one parsed from its event log.
+pub fn store_draft(
+    connection: &mut SqliteConnection,
+    series_id: &str,
+    event_log: &str,
+) -> QueryResult<usize> {
+    let actions = parse_draft(series_id, event_log);
+    connection.transaction(|conn| {
+        diesel::delete(d::draft_actions.filter(d::series_id.eq(series_id))).execute(conn)?;
+        let stored = diesel::insert_into(d::draft_actions)
+            .values(&actions)
+            .execute(conn)?;
+        assign_sides(conn, series_id)?;
+        super::results::score_from_games(conn, series_id)?;
+        Ok(stored)
+    })
+}
+
+/// Records which GRID team played blue and red in each stored game of a series. Each draft
+/// team's side comes from the participants playing its picks; its name is then matched
+/// against the series' team names first, then against every name the two teams have
+/// played under.
+pub fn assign_sides(connection: &mut SqliteConnection, series_id: &str) -> QueryResult<usize> {
+    diesel::sql_query(SIDES_FROM_PARTICIPANTS)
+        .bind::<Text, _>(series_id)
+        .bind::<Text, _>(series_id)
+        .execute(connection)?;
+
+    let assigned = diesel::sql_query(
+        "WITH draft_sides AS (
+             SELECT DISTINCT d.game, d.side,
+                 COALESCE(
+                     CASE d.team_name WHEN s.team1_name THEN s.team1_id
+                                      WHEN s.team2_name THEN s.team2_id END,
+                     (SELECT t.grid_id FROM teams t JOIN team_names n ON n.team_id = t.id
+                      WHERE t.grid_id IN (s.team1_id, s.team2_id) AND n.name = d.team_name)
+                 ) AS team_id
+             FROM draft_actions d
+             JOIN series s ON s.series_id = d.series_id
+             WHERE d.series_id = ?
+         )
+         UPDATE games SET
+             blue_team_id = (SELECT team_id FROM draft_sides ds
+                             WHERE ds.game = games.game AND ds.side = 'blue'
+                               AND ds.team_id IS NOT NULL),
+             red_team_id = (SELECT team_id FROM draft_sides ds
+                            WHERE ds.game = games.game AND ds.side = 'red'
+                              AND ds.team_id IS NOT NULL)
+         WHERE series_id = ?",
+    )
+    .bind::<Text, _>(series_id)
+    .bind::<Text, _>(series_id)
+    .execute(connection)?;
+
+    // With one side known, the other team took the other side.
+    for (known, unknown) in [("blue", "red"), ("red", "blue")] {
+        diesel::sql_query(format!(
+            "UPDATE games SET {unknown}_team_id = (
+                 SELECT CASE games.{known}_team_id WHEN s.team1_id THEN s.team2_id
+                                                   ELSE s.team1_id END
+                 FROM series s WHERE s.series_id = games.series_id
+             ) WHERE series_id = ? AND {known}_team_id IS NOT NULL AND {unknown}_team_id IS NULL"
+        ))
+        .bind::<Text, _>(series_id)
+        .execute(connection)?;
+    }
+    Ok(assigned)
+}
+
+/// Parses drafts for event logs stored before drafts were persisted.
+pub fn backfill_drafts(connection: &mut SqliteConnection) -> QueryResult<usize> {
+    use crate::db::schema::event_logs::dsl as e;
+
+    let missing: Vec<(String, String)> = e::event_logs
+        .filter(diesel::dsl::not(diesel::dsl::exists(
+            d::draft_actions.filter(d::series_id.eq(e::series_id)),
+        )))
+        .select((e::series_id, e::event_log))
+        .load(connection)?;
+
+    let mut stored = 0;
+    for (series_id, event_log) in &missing {
+        stored += store_draft(connection, series_id, event_log)?;
+    }
+    if !missing.is_empty() {
+        info!(
+            "Backfilled {} draft actions for {} series",
+            stored,
+            missing.len()
+        );
+    }
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(kind: &str, chunks: &[&str]) -> Value {
+        let chunks: Vec<Value> = chunks.iter().map(|text| json!({ "text": text })).collect();
+        json!({ "node": { "type": kind, "sentenceChunks": chunks } })
+    }
+
+    fn ban(team: &str, champion: &str) -> Value {
+        event("team-banned-character", &[team, "banned", champion])
+    }
+
+    fn pick(team: &str, champion: &str) -> Value {
+        event("team-picked-character", &[team, "picked", champion])
+    }
+
+    // A full tournament draft: six bans, six picks, four bans, four picks.
+    fn full_game(blue: &str, red: &str) -> Vec<Value> {
+        let teams = "BRBRBR BRRBBR RBRB RBBR".replace(' ', "");
+        let actions = "bbbbbb pppppp bbbb pppp".replace(' ', "");
+        teams
+            .chars()
+            .zip(actions.chars())
+            .enumerate()
+            .map(|(i, (team, action))| {
+                let team = if team == 'B' { blue } else { red };
+                let champion = format!("Champion{}", i + 1);
+                if action == 'b' {
+                    ban(team, &champion)
+                } else {
+                    pick(team, &champion)
+                }
+            })
+            .collect()
+    }
+
+    fn parse(events: Vec<Value>) -> Vec<NewDraftAction> {
+        parse_draft("series", &Value::Array(events).to_string())
+    }
+
+    #[test]
+    fn events_before_validation_are_ignored() {
+        let mut events = vec![pick("Blue", "Ahri"), ban("Red", "Zed")];
+        events.push(event("grid-validated-series", &[]));
+        events.push(ban("Red", "Azir"));
+        let actions = parse(events);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].champion_name, "Azir");
+        assert_eq!(actions[0].sequence, 1);
+        assert_eq!(actions[0].side, "blue");
+    }
+
+    #[test]
+    fn drafts_split_into_two_phases() {
+        let actions = parse(full_game("Blue", "Red"));
+        assert_eq!(actions.len(), 20);
+        let phases = |action: &str| -> Vec<i32> {
+            actions
+                .iter()
+                .filter(|a| a.action == action)
+                .map(|a| a.phase)
+                .collect()
+        };
+        assert_eq!(phases("ban"), [1, 1, 1, 1, 1, 1, 2, 2, 2, 2]);
+        assert_eq!(phases("pick"), [1, 1, 1, 1, 1, 1, 2, 2, 2, 2]);
+        assert!(actions
+            .iter()
+            .all(|a| a.side == if a.team_name == "Blue" { "blue" } else { "red" }));
+        let sequences: Vec<i32> = actions.iter().map(|a| a.sequence).collect();
+        assert_eq!(sequences, (1..=20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn a_new_game_starts_after_ten_picks() {
+        let mut events = full_game("Alpha", "Beta");
+        events.extend(full_game("Beta", "Alpha"));
+        let actions = parse(events);
+        assert_eq!(actions.len(), 40);
+        assert!(actions[..20].iter().all(|a| a.game == 1));
+        assert!(actions[20..].iter().all(|a| a.game == 2));
+
+        let second = &actions[20];
+        assert_eq!((second.sequence, second.phase), (1, 1));
+        assert_eq!(
+            (second.team_name.as_str(), second.side.as_str()),
+            ("Beta", "blue")
+        );
+    }
+
+    #[test]
+    fn short_sentences_and_other_events_are_skipped() {
+        let actions = parse(vec![
+            event("team-banned-character", &["Blue", "banned"]),
+            event("team-picked-character", &[]),
+            event("series-started", &["Blue", "started", "game"]),
+            json!({ "node": { "type": "team-picked-character" } }),
+            pick("Red", "Ahri"),
+        ]);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].team_name, "Red");
+        assert_eq!((actions[0].sequence, actions[0].side.as_str()), (1, "blue"));
+    }
+
+    #[test]
+    fn logs_that_are_not_arrays_have_no_draft() {
+        assert!(parse_draft("series", "{}").is_empty());
+        assert!(parse_draft("series", "not json").is_empty());
+    }
+}
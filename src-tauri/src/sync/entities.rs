@@ -0,0 +1,133 @@
+// src-tauri/sync/entities.rs
+use crate::db::models::{NewPlayer, NewTeam};
+use crate::db::schema::players::dsl as pl;
+use crate::db::schema::teams::dsl as t;
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Nullable, Text};
+use diesel::upsert::excluded;
+
+// Records `name` as an alias seen at `seen_at`, widening the first/last seen window.
+pub fn record_name(
+    connection: &mut SqliteConnection,
+    table: &str,
+    owner_column: &str,
+    owner_id: i32,
+    name: &str,
+    seen_at: Option<&str>,
+) -> QueryResult<()> {
+    let query = format!(
+        r#"
+        INSERT INTO {table} ({owner_column}, name, first_seen, last_seen)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT ({owner_column}, name) DO UPDATE SET
+            first_seen = MIN(COALESCE(first_seen, excluded.first_seen), COALESCE(excluded.first_seen, first_seen)),
+            last_seen = MAX(COALESCE(last_seen, excluded.last_seen), COALESCE(excluded.last_seen, last_seen))
+        "#
+    );
+    diesel::sql_query(query)
+        .bind::<Integer, _>(owner_id)
+        .bind::<Text, _>(name)
+        .bind::<Nullable<Text>, _>(seen_at)
+        .bind::<Nullable<Text>, _>(seen_at)
+        .execute(connection)?;
+    Ok(())
+}
+
+/// Inserts or refreshes a team by GRID id and returns its row id. The stored name is
+/// whichever alias was seen most recently, so syncing older series never renames a team back.
+pub fn upsert_team(
+    connection: &mut SqliteConnection,
+    grid_id: &str,
+    name: &str,
+    logo_url: Option<&str>,
+    seen_at: Option<&str>,
+) -> QueryResult<i32> {
+    let team_id = diesel::insert_into(t::teams)
+        .values(&NewTeam {
+            grid_id,
+            name,
+            logo_url,
+        })
+        .on_conflict(t::grid_id)
+        .do_update()
+        .set(t::logo_url.eq(excluded(t::logo_url)))
+        .returning(t::id)
+        .get_result::<i32>(connection)?;
+    record_name(connection, "team_names", "team_id", team_id, name, seen_at)?;
+    diesel::sql_query(
+        "UPDATE teams SET name = (
+            SELECT name FROM team_names WHERE team_id = teams.id ORDER BY last_seen DESC LIMIT 1
+        ) WHERE id = ?",
+    )
+    .bind::<Integer, _>(team_id)
+    .execute(connection)?;
+    Ok(team_id)
+}
+
+/// Follows `merged_into` links to the identity a player was merged into.
+pub fn resolve_player(connection: &mut SqliteConnection, player_id: i32) -> QueryResult<i32> {
+    let mut current = player_id;
+    // Bounded so a bad manual merge can't loop forever.
+    for _ in 0..16 {
+        let merged_into = pl::players
+            .find(current)
+            .select(pl::merged_into)
+            .first::<Option<i32>>(connection)?;
+        match merged_into {
+            Some(next) if next != current => current = next,
+            _ => break,
+        }
+    }
+    Ok(current)
+}
+
+/// Finds or creates the player behind a summary participant and returns the id that
+/// participants should link to. Players without a puuid are matched by name.
+pub fn upsert_player(
+    connection: &mut SqliteConnection,
+    puuid: Option<&str>,
+    name: &str,
+    seen_at: Option<&str>,
+) -> QueryResult<i32> {
+    let existing = match puuid {
+        Some(puuid) => pl::players
+            .filter(pl::puuid.eq(puuid))
+            .select(pl::id)
+            .first::<i32>(connection)
+            .optional()?,
+        None => pl::players
+            .filter(pl::puuid.is_null())
+            .filter(pl::name.eq(name))
+            .select(pl::id)
+            .first::<i32>(connection)
+            .optional()?,
+    };
+    let player_id = match existing {
+        Some(player_id) => resolve_player(connection, player_id)?,
+        None => diesel::insert_into(pl::players)
+            .values(&NewPlayer { puuid, name })
+            .returning(pl::id)
+            .get_result::<i32>(connection)?,
+    };
+    record_name(
+        connection,
+        "player_names",
+        "player_id",
+        player_id,
+        name,
+        seen_at,
+    )?;
+    refresh_player_name(connection, player_id)?;
+    Ok(player_id)
+}
+
+pub fn refresh_player_name(connection: &mut SqliteConnection, player_id: i32) -> QueryResult<()> {
+    diesel::sql_query(
+        "UPDATE players SET name = (
+            SELECT name FROM player_names WHERE player_id = players.id ORDER BY last_seen DESC LIMIT 1
+        ) WHERE id = ?",
+    )
+    .bind::<Integer, _>(player_id)
+    .execute(connection)?;
+    Ok(())
+}
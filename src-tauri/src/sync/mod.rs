@@ -1,23 +1,35 @@
-use crate::db;
-use crate::db::models::{NewParticipant, NewSeries, Series};
+pub mod draft;
+pub mod entities;
+pub mod results;
+pub mod schedule;
+
+use crate::commands::GameStats;
+use crate::db::models::{NewGame, NewParticipant, NewSeries};
+use crate::db::schema::games::dsl as g;
 use crate::db::schema::participants::dsl as p;
 use crate::db::schema::series::dsl as s;
+use crate::db::{self, DbPool};
+use crate::patch::Patch;
+use crate::settings::{self, ApiEndpoints};
+use crate::timestamp;
 use diesel::prelude::*;
+use diesel::upsert::excluded;
 use log::{error, info, warn};
 use reqwest::Client;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::sleep;
 
-const GRAPHQL_URL: &str = "https://api.grid.gg/central-data/graphql";
 const MAX_RETRIES: u32 = 5;
 const PAGE_SIZE: usize = 50;
 
-pub async fn sync_once(auth_token: String) -> Result<usize, String> {
+pub async fn sync_once(pool: &DbPool, auth_token: String) -> Result<usize, String> {
+    let settings = settings::load_or_default();
+    let api = &settings.api;
     let client = Client::new();
-    let mut inserted_count = 0;
+    let mut synced_count = 0;
     let mut page_cursor: Option<String> = None;
-    let mut connection = db::establish_db_connection();
 
     loop {
         let graphql_query = serde_json::json!({
@@ -25,7 +37,7 @@ pub async fn sync_once(auth_token: String) -> Result<usize, String> {
             "variables": {
                 "first": PAGE_SIZE,
                 "after": page_cursor,
-                "types": vec!["SCRIM"],
+                "types": settings.series_types,
             },
             "query": "query GetHistoricalSeries($first: Int, $after: Cursor, $types: [SeriesType!]) {
                 allSeries(first: $first, after: $after, orderBy: StartTimeScheduled, orderDirection: DESC, 
@@ -54,7 +66,8 @@ pub async fn sync_once(auth_token: String) -> Result<usize, String> {
             }"
         });
 
-        let response = fetch_with_retry(&client, &graphql_query, &auth_token).await?;
+        let response =
+            fetch_with_retry(&client, &api.central_data_url, &graphql_query, &auth_token).await?;
         let json: Value = response.json().await.map_err(|err| err.to_string())?;
         if let Some(error_val) = json.get("errors") {
             if let Some(errors) = error_val.as_array() {
@@ -81,7 +94,11 @@ pub async fn sync_once(auth_token: String) -> Result<usize, String> {
             let series_node = &series_item["node"];
             let series_id_val = series_node["id"].as_str().unwrap_or_default();
             let finished_val = series_node["finished"].as_bool().unwrap_or(false);
-            let start_time_val = series_node["startTimeScheduled"].as_str();
+            // Stored in UTC so dates compare correctly whatever offset GRID reports.
+            let start_time = series_node["startTimeScheduled"]
+                .as_str()
+                .map(|time| timestamp::normalize(time).unwrap_or_else(|| time.to_string()));
+            let start_time_val = start_time.as_deref();
             let default_teams = vec![];
             let teams = series_node["teams"].as_array().unwrap_or(&default_teams);
 
@@ -97,118 +114,103 @@ pub async fn sync_once(auth_token: String) -> Result<usize, String> {
             let team1_id_val = teams[0]["baseInfo"]["id"].as_str();
             let team2_id_val = teams[1]["baseInfo"]["id"].as_str();
 
-            // Try to load an existing series record
-            let existing_series = s::series
-                .filter(s::series_id.eq(series_id_val))
-                .first::<Series>(&mut connection)
-                .optional()
-                .expect("Error loading series");
-
-            if let Some(existing) = existing_series {
-                info!("Found Existing Series for {}", series_id_val);
-                // Compare stored details with the new ones
-                let details_same = existing.finished == finished_val
-                    && existing.start_time_scheduled.as_deref() == start_time_val
-                    && existing.team1_id.as_deref() == team1_id_val
-                    && existing.team2_id.as_deref() == team2_id_val;
-
-                if !details_same {
-                    // Update the record with the new details
-                    let update_result =
-                        diesel::update(s::series.filter(s::series_id.eq(series_id_val)))
-                            .set((
-                                s::finished.eq(finished_val),
-                                s::start_time_scheduled.eq(start_time_val),
-                                s::team1_id.eq(team1_id_val),
-                                s::team2_id.eq(team2_id_val),
-                                s::team1_logo.eq(team1_logo_url),
-                                s::team1_name.eq(team1_name_val),
-                                s::team2_logo.eq(team2_logo_url),
-                                s::team2_name.eq(team2_name_val),
-                            ))
-                            .execute(&mut connection);
-                    match update_result {
-                        Ok(count) => info!(
-                            "Updated series {} with new details ({} row(s) affected).",
-                            series_id_val, count
-                        ),
-                        Err(err) => error!("Failed to update series {}: {}", series_id_val, err),
-                    }
+            let new_series = NewSeries {
+                series_id: series_id_val,
+                finished: finished_val,
+                start_time_scheduled: start_time_val,
+                patch: Some("None"),
+                team1_id: team1_id_val,
+                team1_name: team1_name_val,
+                team1_score: None,
+                team1_logo: team1_logo_url,
+                team2_id: team2_id_val,
+                team2_name: team2_name_val,
+                team2_score: None,
+                team2_logo: team2_logo_url,
+            };
+
+            // Connections are checked out per step so none is held across network calls.
+            let mut connection = db::get_connection(pool)?;
+
+            // Insert new series or refresh the details of existing ones, keeping scores and patch.
+            let upsert_result = diesel::insert_into(s::series)
+                .values(&new_series)
+                .on_conflict(s::series_id)
+                .do_update()
+                .set((
+                    s::finished.eq(excluded(s::finished)),
+                    s::start_time_scheduled.eq(excluded(s::start_time_scheduled)),
+                    s::team1_id.eq(excluded(s::team1_id)),
+                    s::team2_id.eq(excluded(s::team2_id)),
+                    s::team1_logo.eq(excluded(s::team1_logo)),
+                    s::team1_name.eq(excluded(s::team1_name)),
+                    s::team2_logo.eq(excluded(s::team2_logo)),
+                    s::team2_name.eq(excluded(s::team2_name)),
+                ))
+                .returning((s::team1_score, s::team2_score))
+                .get_result::<(Option<i32>, Option<i32>)>(&mut connection);
+            let (team1_score_val, team2_score_val) = match upsert_result {
+                Ok(scores) => {
+                    synced_count += 1;
+                    info!("Upserted series {}.", series_id_val);
+                    scores
                 }
-
-                // If game summary scores are missing, fetch the summary
-                if existing.team1_score.is_none() || existing.team2_score.is_none() {
-                    let result = fetch_game_summary_with_retry(
-                        &client,
-                        series_id_val,
+                Err(err) => {
+                    error!("Failed to upsert series {}: {}", series_id_val, err);
+                    continue; // Skip further processing for this series item
+                }
+            };
+
+            for (team_id, team_name, team_logo) in [
+                (team1_id_val, team1_name_val, team1_logo_url),
+                (team2_id_val, team2_name_val, team2_logo_url),
+            ] {
+                if let (Some(team_id), Some(team_name)) = (team_id, team_name) {
+                    if let Err(err) = entities::upsert_team(
                         &mut connection,
-                        &auth_token,
-                    )
-                    .await;
-                    match result {
-                        Ok(success) => info!(
-                            "Fetched game summary successfully for series {}: {}",
-                            series_id_val, success
-                        ),
-                        Err(err) => error!(
-                            "Failed to fetch game summary for series {}: {}",
-                            series_id_val, err
-                        ),
+                        team_id,
+                        team_name,
+                        team_logo,
+                        start_time_val,
+                    ) {
+                        error!("Failed to upsert team {}: {}", team_id, err);
                     }
                 }
-            } else {
-                // No existing record: insert new series
-                let new_series = NewSeries {
-                    series_id: series_id_val,
-                    finished: finished_val,
-                    start_time_scheduled: start_time_val,
-                    patch: Some("None"),
-                    team1_id: team1_id_val,
-                    team1_name: team1_name_val,
-                    team1_score: None,
-                    team1_logo: team1_logo_url,
-                    team2_id: team2_id_val,
-                    team2_name: team2_name_val,
-                    team2_score: None,
-                    team2_logo: team2_logo_url,
-                };
-
-                match diesel::insert_into(s::series)
-                    .values(&new_series)
-                    .execute(&mut connection)
-                {
-                    Ok(count) => {
-                        inserted_count += count;
-                        info!("Inserted new series {}.", series_id_val);
-                    }
-                    Err(err) => {
-                        error!("Failed to insert series {}: {}", series_id_val, err);
-                        continue; // Skip further processing for this series item
-                    }
+            }
+            // Series still being played gain games, and a stored score can count games whose
+            // summaries are missing, so those are fetched again along with unscored series.
+            let stored_games = g::games
+                .filter(g::series_id.eq(series_id_val))
+                .count()
+                .get_result::<i64>(&mut connection)
+                .unwrap_or_else(|err| {
+                    error!("Failed to count games of series {}: {}", series_id_val, err);
+                    0
+                });
+            drop(connection);
+
+            let needs_summary = match (team1_score_val, team2_score_val) {
+                (Some(team1), Some(team2)) => {
+                    !finished_val || stored_games < i64::from(team1 + team2)
                 }
-
-                // After inserting, run fetch_game_summary_with_retry to populate scores and other details
-                let result = fetch_game_summary_with_retry(
-                    &client,
-                    series_id_val,
-                    &mut connection,
-                    &auth_token,
-                )
-                .await;
+                _ => true,
+            };
+            if needs_summary {
+                let result =
+                    fetch_game_summary_with_retry(&client, api, series_id_val, pool, &auth_token)
+                        .await;
                 match result {
                     Ok(success) => info!(
-                        "Fetched game summary successfully for new series {}: {}",
+                        "Fetched game summary successfully for series {}: {}",
                         series_id_val, success
                     ),
                     Err(err) => error!(
-                        "Failed to fetch game summary for new series {}: {}",
+                        "Failed to fetch game summary for series {}: {}",
                         series_id_val, err
                     ),
                 }
             }
-            match fetch_and_store_event_log(&client, series_id_val, &mut connection, &auth_token)
-                .await
-            {
+            match fetch_and_store_event_log(&client, api, series_id_val, pool, &auth_token).await {
                 Ok(_) => info!("Event log saved for series {}", series_id_val),
                 Err(err) => error!(
                     "Failed to save event log for series {}: {}",
@@ -224,17 +226,17 @@ pub async fn sync_once(auth_token: String) -> Result<usize, String> {
         page_cursor = page_info["endCursor"].as_str().map(|s| s.to_string());
     }
 
-    info!("Synced {} series", inserted_count);
-    Ok(inserted_count)
+    info!("Synced {} series", synced_count);
+    Ok(synced_count)
 }
 
 async fn fetch_and_store_event_log(
     client: &Client,
+    api: &ApiEndpoints,
     series_id: &str,
-    connection: &mut SqliteConnection,
+    pool: &DbPool,
     auth_token: &str,
 ) -> Result<(), String> {
-    let event_log_url = "https://lol.grid.gg/api/event-explorer-api/events/graphql";
     let graphql_query = serde_json::json!({
         "operationName": "getSeriesEvents",
         "variables": {
@@ -265,7 +267,7 @@ async fn fetch_and_store_event_log(
     let mut attempts = 0;
     while attempts < MAX_RETRIES {
         let response = client
-            .post(event_log_url)
+            .post(&api.event_log_url)
             .header("Content-Type", "application/json")
             .header("Authorization", format!("Bearer {}", auth_token))
             .json(&graphql_query)
@@ -278,42 +280,30 @@ async fn fetch_and_store_event_log(
                 let event_edges = json["data"]["events"]["edges"].clone();
                 let event_log_str =
                     serde_json::to_string(&event_edges).map_err(|err| err.to_string())?;
+                let mut connection = db::get_connection(pool)?;
 
                 // Insert or update the event log in the database.
                 use crate::db::schema::event_logs::dsl as e;
-                let existing_event_log = e::event_logs
-                    .filter(e::series_id.eq(series_id))
-                    .first::<crate::db::models::EventLog>(connection)
-                    .optional()
-                    .expect("Error loading event log");
-
-                if let Some(_) = existing_event_log {
-                    let update_result =
-                        diesel::update(e::event_logs.filter(e::series_id.eq(series_id)))
-                            .set(crate::db::schema::event_logs::event_log.eq(&event_log_str))
-                            .execute(connection);
-                    match update_result {
-                        Ok(_) => info!("Updated event log for series {}", series_id),
-                        Err(err) => error!(
-                            "Failed to update event log for series {}: {}",
-                            series_id, err
-                        ),
-                    }
-                } else {
-                    let new_event_log = crate::db::models::NewEventLog {
-                        series_id,
-                        event_log: &event_log_str,
-                    };
-                    match diesel::insert_into(e::event_logs)
-                        .values(&new_event_log)
-                        .execute(connection)
-                    {
-                        Ok(_) => info!("Inserted event log for series {}", series_id),
-                        Err(err) => error!(
-                            "Failed to insert event log for series {}: {}",
-                            series_id, err
-                        ),
-                    }
+                let new_event_log = crate::db::models::NewEventLog {
+                    series_id,
+                    event_log: &event_log_str,
+                };
+                match diesel::insert_into(e::event_logs)
+                    .values(&new_event_log)
+                    .on_conflict(e::series_id)
+                    .do_update()
+                    .set(e::event_log.eq(excluded(e::event_log)))
+                    .execute(&mut connection)
+                {
+                    Ok(_) => info!("Upserted event log for series {}", series_id),
+                    Err(err) => error!(
+                        "Failed to upsert event log for series {}: {}",
+                        series_id, err
+                    ),
+                }
+                match draft::store_draft(&mut connection, series_id, &event_log_str) {
+                    Ok(count) => info!("Stored {} draft actions for series {}", count, series_id),
+                    Err(err) => error!("Failed to store draft for series {}: {}", series_id, err),
                 }
                 return Ok(());
             }
@@ -344,16 +334,69 @@ async fn fetch_and_store_event_log(
 
 async fn fetch_game_summary_with_retry(
     client: &Client,
+    api: &ApiEndpoints,
     fetch_series_id: &str,
-    connection: &mut SqliteConnection,
+    pool: &DbPool,
     auth_token: &str,
 ) -> Result<String, String> {
     let (team1_score_val, team2_score_val) =
-        fetch_series_scores(client, fetch_series_id, auth_token).await?;
+        fetch_series_scores(client, api, fetch_series_id, auth_token).await?;
+
+    // One summary per game played. A series without a score yet still has its first game.
+    let game_count = (team1_score_val + team2_score_val).max(1);
+    let game_version =
+        fetch_game_summary(client, api, fetch_series_id, 1, pool, auth_token).await?;
+    for game in 2..=game_count {
+        if let Err(err) =
+            fetch_game_summary(client, api, fetch_series_id, game, pool, auth_token).await
+        {
+            warn!(
+                "Failed to fetch game {} summary for series {}: {}",
+                game, fetch_series_id, err
+            );
+        }
+    }
+    let patch = Patch::parse(&game_version);
+
+    let mut connection = db::get_connection(pool)?;
+    match diesel::update(s::series.filter(s::series_id.eq(fetch_series_id)))
+        .set((
+            s::team1_score.eq(team1_score_val),
+            s::team2_score.eq(team2_score_val),
+            s::patch.eq(game_version.clone()),
+            s::patch_major.eq(patch.map(|patch| patch.major)),
+            s::patch_minor.eq(patch.map(|patch| patch.minor)),
+        ))
+        .execute(&mut connection)
+    {
+        Ok(_) => info!("Updated scores for series {}", fetch_series_id),
+        Err(err) => warn!(
+            "Failed to update scores for series {}: {}",
+            fetch_series_id, err
+        ),
+    }
+    // GRID's score is replaced by the games each team won once all of them are stored.
+    if let Err(err) = results::score_from_games(&mut connection, fetch_series_id) {
+        warn!(
+            "Failed to score series {} from its games: {}",
+            fetch_series_id, err
+        );
+    }
+    Ok(game_version)
+}
 
+// Stores the participants and result of one game, returning its game version.
+async fn fetch_game_summary(
+    client: &Client,
+    api: &ApiEndpoints,
+    fetch_series_id: &str,
+    game: i32,
+    pool: &DbPool,
+    auth_token: &str,
+) -> Result<String, String> {
     let summary_url = format!(
-        "https://api.grid.gg/file-download/end-state/riot/series/{}/games/1/summary",
-        fetch_series_id
+        "{}/end-state/riot/series/{}/games/{}/summary",
+        api.file_download_url, fetch_series_id, game
     );
     let mut attempts = 0;
 
@@ -373,10 +416,21 @@ async fn fetch_game_summary_with_retry(
                     .as_str()
                     .map(String::from)
                     .unwrap_or_else(|| "latest".to_string());
-                // STILL NEED TO FETCH THE SCORES
+
+                let mut connection = db::get_connection(pool)?;
+
+                // Player aliases are dated by when the series was played.
+                let played_at = s::series
+                    .filter(s::series_id.eq(fetch_series_id))
+                    .select(s::start_time_scheduled)
+                    .first::<Option<String>>(&mut connection)
+                    .optional()
+                    .map_err(|err| err.to_string())?
+                    .flatten();
 
                 // ✅ Extract participants like the frontend does
                 if let Some(participants_array) = summary_json["participants"].as_array() {
+                    let mut team_slots: HashMap<Option<i32>, usize> = HashMap::new();
                     for participant_item in participants_array {
                         // Extract a unique identifier for the participant
                         let player_id_val = participant_item["summonerId"]
@@ -384,73 +438,131 @@ async fn fetch_game_summary_with_retry(
                             .map(|num| num.to_string())
                             .unwrap_or_default();
 
-                        // Check if this participant already exists for the given series
-                        let existing_participant = p::participants
-                            .filter(p::series_id.eq(fetch_series_id))
-                            .filter(p::player_id.eq(&player_id_val))
-                            .first::<crate::db::models::Participant>(connection)
-                            .optional()
-                            .expect("Error loading participant");
-
-                        if existing_participant.is_none() {
-                            // Participant doesn't exist—insert it
-                            let stats_json_val =
-                                serde_json::to_string(participant_item).unwrap_or_default();
-                            let new_participant = NewParticipant {
-                                series_id: fetch_series_id.to_string(),
-                                player_id: player_id_val.clone(),
-                                player_name: participant_item["riotIdGameName"]
-                                    .as_str()
-                                    .unwrap_or("")
-                                    .to_string(),
-                                champion_name: participant_item["championName"]
-                                    .as_str()
-                                    .unwrap_or("")
-                                    .to_string(),
-                                stats_json: stats_json_val,
-                            };
-
-                            match diesel::insert_into(p::participants)
-                                .values(&new_participant)
-                                .execute(connection)
-                            {
-                                Ok(_) => {
-                                    info!(
-                                        "Inserted participant {} for series {}.",
-                                        player_id_val, fetch_series_id
-                                    );
-                                }
-                                Err(err) => {
-                                    error!(
-                                        "Failed to insert participant for series {}: {}",
-                                        fetch_series_id, err
-                                    );
+                        let stats_json_val =
+                            serde_json::to_string(participant_item).unwrap_or_default();
+                        let stats: GameStats = serde_json::from_value(participant_item.clone())
+                            .unwrap_or_else(|err| {
+                                warn!(
+                                    "Failed to parse stats for participant {} in series {}: {}",
+                                    player_id_val, fetch_series_id, err
+                                );
+                                // Keep who played what even when the stats don't parse.
+                                let text = |key: &str| {
+                                    participant_item[key].as_str().unwrap_or("").to_string()
+                                };
+                                GameStats {
+                                    riot_id_game_name: text("riotIdGameName"),
+                                    champion_name: text("championName"),
+                                    ..GameStats::default()
                                 }
+                            });
+                        let slot = team_slots.entry(stats.team_id).or_default();
+                        let role = stats.role(*slot);
+                        *slot += 1;
+                        let player_ref = match entities::upsert_player(
+                            &mut connection,
+                            stats.puuid.as_deref(),
+                            &stats.riot_id_game_name,
+                            played_at.as_deref(),
+                        ) {
+                            Ok(player_ref) => Some(player_ref),
+                            Err(err) => {
+                                error!(
+                                    "Failed to upsert player {} for series {}: {}",
+                                    stats.riot_id_game_name, fetch_series_id, err
+                                );
+                                None
+                            }
+                        };
+                        let new_participant = NewParticipant {
+                            series_id: fetch_series_id.to_string(),
+                            game,
+                            player_id: player_id_val.clone(),
+                            player_name: stats.riot_id_game_name.clone(),
+                            champion_name: stats.champion_name.clone(),
+                            stats_json: stats_json_val,
+                            kills: stats.kills,
+                            deaths: stats.deaths,
+                            assists: stats.assists,
+                            total_minions_killed: stats.total_minions_killed,
+                            neutral_minions_killed: stats.neutral_minions_killed,
+                            gold_earned: stats.gold_earned,
+                            total_damage_dealt_to_champions: stats.total_damage_dealt_to_champions,
+                            total_damage_taken: stats.total_damage_taken,
+                            vision_score: stats.vision_score,
+                            wards_placed: stats.wards_placed,
+                            wards_killed: stats.wards_killed,
+                            detector_wards_placed: stats.detector_wards_placed,
+                            item0: stats.item0,
+                            item1: stats.item1,
+                            item2: stats.item2,
+                            item3: stats.item3,
+                            item4: stats.item4,
+                            item5: stats.item5,
+                            item6: stats.item6,
+                            primary_rune_style: stats.primary_rune_style(),
+                            keystone: stats.keystone(),
+                            secondary_rune_style: stats.secondary_rune_style(),
+                            team_position: stats
+                                .team_position
+                                .clone()
+                                .filter(|pos| !pos.is_empty()),
+                            team_id: stats.team_id,
+                            win: stats.win,
+                            puuid: stats.puuid.clone(),
+                            player_ref,
+                            role: role.map(|role| role.as_str().to_string()),
+                        };
+
+                        match diesel::insert_into(p::participants)
+                            .values(&new_participant)
+                            .on_conflict((p::series_id, p::game, p::player_id))
+                            .do_update()
+                            .set(&new_participant)
+                            .execute(&mut connection)
+                        {
+                            Ok(_) => {
+                                info!(
+                                    "Upserted participant {} for series {}.",
+                                    player_id_val, fetch_series_id
+                                );
+                            }
+                            Err(err) => {
+                                error!(
+                                    "Failed to upsert participant for series {}: {}",
+                                    fetch_series_id, err
+                                );
                             }
-                        } else {
-                            // Participant already exists: skip insertion.
-                            info!(
-                                "Participant {} already exists for series {}.",
-                                player_id_val, fetch_series_id
-                            );
                         }
                     }
                 }
 
-                match diesel::update(s::series.filter(s::series_id.eq(fetch_series_id)))
-                    .set((
-                        s::team1_score.eq(team1_score_val),
-                        s::team2_score.eq(team2_score_val),
-                        s::patch.eq(game_version.clone()),
-                    ))
-                    .execute(connection)
+                let new_game = NewGame {
+                    series_id: fetch_series_id,
+                    game,
+                    duration_seconds: game_duration_seconds(&summary_json),
+                    winning_side: winning_side(&summary_json),
+                };
+                match diesel::insert_into(g::games)
+                    .values(&new_game)
+                    .on_conflict((g::series_id, g::game))
+                    .do_update()
+                    .set(&new_game)
+                    .execute(&mut connection)
                 {
-                    Ok(_) => info!("Updated scores for series {}", fetch_series_id),
-                    Err(err) => warn!(
-                        "Failed to update scores for series {}: {}",
+                    Ok(_) => info!("Upserted game {} for series {}", game, fetch_series_id),
+                    Err(err) => error!(
+                        "Failed to upsert game for series {}: {}",
                         fetch_series_id, err
                     ),
                 }
+                // The draft may have been stored by an earlier sync, before this game existed.
+                if let Err(err) = draft::assign_sides(&mut connection, fetch_series_id) {
+                    error!(
+                        "Failed to assign sides for series {}: {}",
+                        fetch_series_id, err
+                    );
+                }
 
                 return Ok(game_version);
             }
@@ -476,15 +588,56 @@ async fn fetch_game_summary_with_retry(
     Err("Max retries reached".to_string())
 }
 
+// Riot team ids are 100 for blue and 200 for red. Falls back to the participants when the
+// summary has no team results.
+fn winning_side(summary_json: &Value) -> Option<&'static str> {
+    let winner = summary_json["teams"]
+        .as_array()
+        .into_iter()
+        .chain(summary_json["participants"].as_array())
+        .flatten()
+        .find(|entry| entry["win"].as_bool() == Some(true))?;
+    match winner["teamId"].as_i64()? {
+        100 => Some("blue"),
+        200 => Some("red"),
+        _ => None,
+    }
+}
+
+// The longest timePlayed, the same rule the create_games migration backfills with, so games
+// synced before and after it agree. Summaries without participant times fall back to
+// gameDuration: milliseconds for games without gameEndTimestamp (pre-11.20), seconds after.
+fn game_duration_seconds(summary_json: &Value) -> Option<i32> {
+    let played = summary_json["participants"]
+        .as_array()
+        .and_then(|participants| {
+            participants
+                .iter()
+                .filter_map(|participant| participant["timePlayed"].as_i64())
+                .max()
+        });
+    played
+        .or_else(|| {
+            let duration = summary_json["gameDuration"].as_i64()?;
+            Some(if summary_json.get("gameEndTimestamp").is_some() {
+                duration
+            } else {
+                duration / 1000
+            })
+        })
+        .map(|duration| duration as i32)
+}
+
 async fn fetch_with_retry(
     client: &Client,
+    url: &str,
     body: &serde_json::Value,
     auth_token: &str, // ✅ Pass auth token
 ) -> Result<reqwest::Response, String> {
     let mut attempts = 0;
     while attempts < MAX_RETRIES {
         let response = client
-            .post(GRAPHQL_URL)
+            .post(url)
             .header("Content-Type", "application/json")
             .header("Authorization", format!("Bearer {}", auth_token)) // ✅ Add Auth Header
             .json(body)
@@ -519,6 +672,7 @@ async fn fetch_with_retry(
 
 async fn fetch_series_scores(
     client: &Client,
+    api: &ApiEndpoints,
     fetch_series_id: &str,
     auth_token: &str,
 ) -> Result<(i32, i32), String> {
@@ -538,7 +692,7 @@ async fn fetch_series_scores(
     });
 
     let response = client
-        .post("https://api.grid.gg/live-data-feed/series-state/graphql")
+        .post(&api.series_state_url)
         .header("Content-Type", "application/json")
         .header("Authorization", format!("Bearer {}", auth_token))
         .json(&graphql_query)
@@ -0,0 +1,27 @@
+// src-tauri/sync/results.rs
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+// The GRID team that won a game in `games g`, once its sides are known.
+const GAME_WINNER: &str = "CASE g.winning_side WHEN 'blue' THEN g.blue_team_id
+                                               WHEN 'red' THEN g.red_team_id END";
+
+/// Recounts a series' score from the games each team won. Only applies once every game
+/// played is stored with a known winner, so a partial sync keeps the GRID score. A tie
+/// stays a tie, e.g. 1-1 in a two game scrim.
+pub fn score_from_games(connection: &mut SqliteConnection, series_id: &str) -> QueryResult<usize> {
+    diesel::sql_query(format!(
+        "UPDATE series SET
+             team1_score = (SELECT COUNT(*) FROM games g WHERE g.series_id = series.series_id
+                            AND {GAME_WINNER} = series.team1_id),
+             team2_score = (SELECT COUNT(*) FROM games g WHERE g.series_id = series.series_id
+                            AND {GAME_WINNER} = series.team2_id)
+         WHERE series_id = ?
+           AND (SELECT COUNT(*) FROM games g WHERE g.series_id = series.series_id)
+                   >= MAX(COALESCE(team1_score, 0) + COALESCE(team2_score, 0), 1)
+           AND NOT EXISTS (SELECT 1 FROM games g WHERE g.series_id = series.series_id
+                           AND {GAME_WINNER} IS NULL)"
+    ))
+    .bind::<Text, _>(series_id)
+    .execute(connection)
+}
@@ -0,0 +1,286 @@
+// src-tauri/sync/schedule.rs
+use chrono::{Datelike, Duration as ChronoDuration, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const DEFAULT_INTERVAL_MINUTES: u64 = 10;
+const DEFAULT_SCRIM_INTERVAL_MINUTES: u64 = 2;
+const DEFAULT_POST_SCRIM_DELAY_MINUTES: u64 = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String, // "HH:MM", local time
+    pub end: String,   // "HH:MM", may be before `start` to wrap past midnight
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrimBlock {
+    pub days: Vec<Weekday>, // e.g. ["Mon", "Wed"]
+    pub start: String,      // "HH:MM", local time
+    pub end: String,        // "HH:MM", local time
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSchedule {
+    pub interval_minutes: u64,
+    pub quiet_hours: Option<QuietHours>,
+    pub scrim_blocks: Vec<ScrimBlock>,
+    pub scrim_interval_minutes: u64,
+    pub sync_after_scrim_blocks: bool,
+    pub post_scrim_delay_minutes: u64,
+}
+
+impl Default for SyncSchedule {
+    fn default() -> Self {
+        SyncSchedule {
+            interval_minutes: DEFAULT_INTERVAL_MINUTES,
+            quiet_hours: None,
+            scrim_blocks: Vec::new(),
+            scrim_interval_minutes: DEFAULT_SCRIM_INTERVAL_MINUTES,
+            sync_after_scrim_blocks: false,
+            post_scrim_delay_minutes: DEFAULT_POST_SCRIM_DELAY_MINUTES,
+        }
+    }
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|err| format!("Invalid time '{}' (expected HH:MM): {}", value, err))
+}
+
+// True if `time` falls inside [start, end), wrapping past midnight when end <= start.
+fn in_window(time: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start <= end {
+        time >= start && time < end
+    } else {
+        time >= start || time < end
+    }
+}
+
+impl SyncSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_minutes == 0 {
+            return Err("Sync interval must be at least 1 minute".to_string());
+        }
+        if self.scrim_interval_minutes == 0 {
+            return Err("Scrim sync interval must be at least 1 minute".to_string());
+        }
+        if let Some(ref quiet) = self.quiet_hours {
+            parse_time(&quiet.start)?;
+            parse_time(&quiet.end)?;
+        }
+        for block in &self.scrim_blocks {
+            let start = parse_time(&block.start)?;
+            let end = parse_time(&block.end)?;
+            if end <= start {
+                return Err(format!(
+                    "Scrim block {}-{} must end after it starts",
+                    block.start, block.end
+                ));
+            }
+            if block.days.is_empty() {
+                return Err(format!(
+                    "Scrim block {}-{} has no days selected",
+                    block.start, block.end
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_quiet(&self, now: NaiveDateTime) -> bool {
+        let Some(ref quiet) = self.quiet_hours else {
+            return false;
+        };
+        let (Ok(start), Ok(end)) = (parse_time(&quiet.start), parse_time(&quiet.end)) else {
+            return false;
+        };
+        // Scrim blocks take priority, so scrims that run into quiet hours are still picked up.
+        in_window(now.time(), start, end)
+            && !self.in_scrim_block(now)
+            && !self.in_post_block_sync(now)
+    }
+
+    /// How long to sleep during quiet hours: until they end, or until a scrim block starts
+    /// or its post-block sync is due, whichever comes first.
+    pub fn until_quiet_end(&self, now: NaiveDateTime) -> Duration {
+        let end = self
+            .quiet_hours
+            .as_ref()
+            .and_then(|quiet| parse_time(&quiet.end).ok())
+            .unwrap_or(now.time());
+        let mut wake = now.date().and_time(end);
+        if wake <= now {
+            wake += ChronoDuration::days(1);
+        }
+        let post_delay = ChronoDuration::minutes(self.post_scrim_delay_minutes as i64);
+        for (start, end) in self.block_windows(now) {
+            if start > now && start < wake {
+                wake = start;
+            }
+            let after = end + post_delay;
+            if self.sync_after_scrim_blocks && after > now && after < wake {
+                wake = after;
+            }
+        }
+        to_std(wake - now)
+    }
+
+    fn in_scrim_block(&self, now: NaiveDateTime) -> bool {
+        self.block_windows(now)
+            .any(|(start, end)| now >= start && now < end)
+    }
+
+    // True for one scrim interval once the post-block delay has passed, so the sync after a
+    // block still runs when the block ends inside quiet hours.
+    fn in_post_block_sync(&self, now: NaiveDateTime) -> bool {
+        if !self.sync_after_scrim_blocks {
+            return false;
+        }
+        let post_delay = ChronoDuration::minutes(self.post_scrim_delay_minutes as i64);
+        let window = ChronoDuration::minutes(self.scrim_interval_minutes as i64);
+        self.block_windows(now).any(|(_, end)| {
+            let due = end + post_delay;
+            now >= due && now < due + window
+        })
+    }
+
+    // Scrim block occurrences for yesterday, today and tomorrow as local datetimes.
+    fn block_windows(
+        &self,
+        now: NaiveDateTime,
+    ) -> impl Iterator<Item = (NaiveDateTime, NaiveDateTime)> + '_ {
+        let today = now.date();
+        (-1..=1).flat_map(move |offset| {
+            let date = today + ChronoDuration::days(offset);
+            self.scrim_blocks.iter().filter_map(move |block| {
+                if !block.days.contains(&date.weekday()) {
+                    return None;
+                }
+                let start = parse_time(&block.start).ok()?;
+                let end = parse_time(&block.end).ok()?;
+                Some((date.and_time(start), date.and_time(end)))
+            })
+        })
+    }
+
+    /// How long to wait after a sync finishing at `now` before the next one.
+    pub fn next_delay(&self, now: NaiveDateTime) -> Duration {
+        if self.in_scrim_block(now) {
+            return Duration::from_secs(self.scrim_interval_minutes * 60);
+        }
+
+        let mut wake = now + ChronoDuration::minutes(self.interval_minutes as i64);
+        let post_delay = ChronoDuration::minutes(self.post_scrim_delay_minutes as i64);
+        for (start, end) in self.block_windows(now) {
+            // Wake up as soon as a block starts, and once more shortly after it ends.
+            if start > now && start < wake {
+                wake = start;
+            }
+            let after = end + post_delay;
+            if self.sync_after_scrim_blocks && after > now && after < wake {
+                wake = after;
+            }
+        }
+        to_std(wake - now)
+    }
+}
+
+fn to_std(duration: ChronoDuration) -> Duration {
+    duration
+        .to_std()
+        .unwrap_or_else(|_| Duration::from_secs(DEFAULT_INTERVAL_MINUTES * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    // 2024-05-06 is a Monday.
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, day)
+            .unwrap()
+            .and_time(parse_time(time).unwrap())
+    }
+
+    fn minutes(minutes: u64) -> Duration {
+        Duration::from_secs(minutes * 60)
+    }
+
+    // Interval 10, scrim interval 2, a Monday block 23:00-23:30 and quiet hours 22:00-08:00.
+    fn schedule(sync_after_scrim_blocks: bool) -> SyncSchedule {
+        SyncSchedule {
+            quiet_hours: Some(QuietHours {
+                start: "22:00".to_string(),
+                end: "08:00".to_string(),
+            }),
+            scrim_blocks: vec![ScrimBlock {
+                days: vec![Weekday::Mon],
+                start: "23:00".to_string(),
+                end: "23:30".to_string(),
+            }],
+            sync_after_scrim_blocks,
+            ..SyncSchedule::default()
+        }
+    }
+
+    #[test]
+    fn next_delay_uses_the_regular_interval_away_from_blocks() {
+        assert_eq!(schedule(true).next_delay(at(6, "12:00")), minutes(10));
+        assert_eq!(
+            SyncSchedule::default().next_delay(at(6, "22:55")),
+            minutes(10)
+        );
+    }
+
+    #[test]
+    fn next_delay_uses_the_scrim_interval_inside_a_block() {
+        assert_eq!(schedule(false).next_delay(at(6, "23:10")), minutes(2));
+        // The block is on Mondays only.
+        assert_eq!(schedule(false).next_delay(at(7, "23:10")), minutes(10));
+    }
+
+    #[test]
+    fn next_delay_wakes_when_a_block_starts() {
+        assert_eq!(schedule(false).next_delay(at(6, "22:55")), minutes(5));
+        assert_eq!(schedule(true).next_delay(at(6, "22:55")), minutes(5));
+    }
+
+    #[test]
+    fn next_delay_wakes_for_the_post_block_sync_only_when_enabled() {
+        assert_eq!(schedule(true).next_delay(at(6, "23:40")), minutes(5));
+        assert_eq!(schedule(false).next_delay(at(6, "23:40")), minutes(10));
+    }
+
+    #[test]
+    fn is_quiet_inside_quiet_hours_except_for_blocks() {
+        let schedule = schedule(true);
+        assert!(!schedule.is_quiet(at(6, "21:59")));
+        assert!(schedule.is_quiet(at(6, "22:00")));
+        assert!(schedule.is_quiet(at(7, "07:59")));
+        assert!(!schedule.is_quiet(at(7, "08:00")));
+        assert!(!schedule.is_quiet(at(6, "23:10")));
+        // The post-block sync is due at 23:45 and lasts one scrim interval.
+        assert!(schedule.is_quiet(at(6, "23:40")));
+        assert!(!schedule.is_quiet(at(6, "23:46")));
+        assert!(schedule.is_quiet(at(6, "23:47")));
+        assert!(!SyncSchedule::default().is_quiet(at(6, "23:00")));
+    }
+
+    #[test]
+    fn until_quiet_end_stops_at_blocks_and_post_block_syncs() {
+        assert_eq!(schedule(true).until_quiet_end(at(6, "22:10")), minutes(50));
+        assert_eq!(schedule(true).until_quiet_end(at(6, "23:35")), minutes(10));
+        assert_eq!(
+            schedule(false).until_quiet_end(at(6, "23:35")),
+            minutes(8 * 60 + 25)
+        );
+        // No block on Tuesday night, so sleep until the morning.
+        assert_eq!(
+            schedule(true).until_quiet_end(at(7, "22:00")),
+            minutes(10 * 60)
+        );
+    }
+}
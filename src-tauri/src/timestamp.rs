@@ -0,0 +1,247 @@
+// src-tauri/timestamp.rs
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// How timestamps are stored: UTC to the second, so they compare correctly as text.
+pub const FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// An ISO 8601 timestamp with any offset and precision, e.g. "2024-05-01T23:30:00+02:00"
+/// or "2024-05-01T21:30:00.000Z". Timestamps without an offset are taken as UTC.
+pub fn parse(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").map(|time| time.and_utc())
+        })
+        .ok()
+}
+
+/// `value` in the stored form, or None if it isn't a timestamp.
+pub fn normalize(value: &str) -> Option<String> {
+    parse(value).map(|time| time.format(FORMAT).to_string())
+}
+
+/// The timezone dates and weekdays are read in. `Local` is this computer's timezone,
+/// daylight saving included; IANA names aren't supported, so other zones are fixed offsets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timezone {
+    #[default]
+    Local,
+    Offset(i32), // seconds east of UTC
+}
+
+impl Timezone {
+    /// The SQLite date modifier that shifts a stored UTC timestamp into this timezone.
+    pub fn modifier(self) -> String {
+        match self {
+            Timezone::Local => "localtime".to_string(),
+            Timezone::Offset(seconds) => format!("{:+} seconds", seconds),
+        }
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Timezone::Local => write!(f, "local"),
+            Timezone::Offset(0) => write!(f, "UTC"),
+            Timezone::Offset(seconds) => {
+                let sign = if seconds < 0 { '-' } else { '+' };
+                let minutes = seconds.abs() / 60;
+                write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    // "local", "UTC", or an offset such as "+02:00", "-0530" or "UTC+2".
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "'{}' is not a timezone; use 'local', 'UTC' or an offset such as +02:00",
+                value
+            )
+        };
+        let trimmed = value.trim();
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("local") {
+            return Ok(Timezone::Local);
+        }
+        let offset = ["UTC", "GMT", "Z"]
+            .iter()
+            .find_map(|prefix| {
+                trimmed
+                    .get(..prefix.len())
+                    .filter(|start| start.eq_ignore_ascii_case(prefix))
+                    .map(|_| &trimmed[prefix.len()..])
+            })
+            .unwrap_or(trimmed);
+        if offset.is_empty() {
+            return Ok(Timezone::Offset(0));
+        }
+        let (sign, offset) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+            (Some(rest), _) => (1, rest),
+            (_, Some(rest)) => (-1, rest),
+            _ => return Err(invalid()),
+        };
+        if !offset.chars().all(|c| c.is_ascii_digit() || c == ':') {
+            return Err(invalid());
+        }
+        let (hours, minutes) = match offset.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if offset.len() > 2 => offset.split_at(offset.len() - 2),
+            None => (offset, "0"),
+        };
+        let (Ok(hours), Ok(minutes)) = (hours.parse::<i32>(), minutes.parse::<i32>()) else {
+            return Err(invalid());
+        };
+        if hours > 14 || minutes > 59 {
+            return Err(invalid());
+        }
+        Ok(Timezone::Offset(sign * (hours * 3600 + minutes * 60)))
+    }
+}
+
+impl Serialize for Timezone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timezone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// One end of a date range: a calendar day in the filter's timezone ("2024-05-01"), or an
+/// exact instant ("2024-05-01T08:00:00.000Z").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+    Day(NaiveDate),
+    Instant(DateTime<Utc>),
+}
+
+impl fmt::Display for DateBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateBound::Day(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            DateBound::Instant(time) => write!(f, "{}", time.format(FORMAT)),
+        }
+    }
+}
+
+impl FromStr for DateBound {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map(DateBound::Day)
+            .ok()
+            .or_else(|| parse(value).map(DateBound::Instant))
+            .ok_or_else(|| {
+                format!(
+                    "'{}' is not a date such as 2024-05-01 or 2024-05-01T08:00:00Z",
+                    value
+                )
+            })
+    }
+}
+
+impl Serialize for DateBound {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateBound {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hours(hours: f64) -> Timezone {
+        Timezone::Offset((hours * 3600.0) as i32)
+    }
+
+    #[test]
+    fn timezones_parse_names_and_offsets() {
+        for (value, expected) in [
+            ("", Timezone::Local),
+            ("local", Timezone::Local),
+            (" Local ", Timezone::Local),
+            ("UTC", hours(0.0)),
+            ("gmt", hours(0.0)),
+            ("Z", hours(0.0)),
+            ("+02:00", hours(2.0)),
+            ("UTC+2", hours(2.0)),
+            ("+0530", hours(5.5)),
+            ("-05:30", hours(-5.5)),
+            ("-5", hours(-5.0)),
+            ("+14:00", hours(14.0)),
+        ] {
+            assert_eq!(value.parse::<Timezone>(), Ok(expected), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn malformed_timezones_are_errors() {
+        for value in [
+            "-", "+:", "+15", "UTC+15", "+02:60", "0200", "CET", "+2h", "UTC 2", "+-2",
+        ] {
+            assert!(value.parse::<Timezone>().is_err(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn timezones_display_as_they_parse() {
+        for zone in [Timezone::Local, hours(0.0), hours(5.5), hours(-9.5)] {
+            assert_eq!(zone.to_string().parse::<Timezone>(), Ok(zone));
+        }
+        assert_eq!(hours(-5.5).to_string(), "-05:30");
+    }
+
+    #[test]
+    fn timestamps_parse_with_any_offset() {
+        let expected = parse("2024-05-01T21:30:00Z").unwrap();
+        for value in [
+            "2024-05-01T23:30:00+02:00",
+            "2024-05-01T21:30:00.000Z",
+            " 2024-05-01T16:30:00-05:00 ",
+        ] {
+            assert_eq!(parse(value), Some(expected), "{:?}", value);
+        }
+        assert_eq!(
+            normalize("2024-05-01T23:30:00.250+02:00").as_deref(),
+            Some("2024-05-01T21:30:00Z")
+        );
+    }
+
+    #[test]
+    fn timestamps_without_an_offset_are_utc() {
+        assert_eq!(
+            parse("2024-05-01T21:30:00"),
+            parse("2024-05-01T21:30:00+00:00")
+        );
+        assert_eq!(
+            normalize("2024-05-01T21:30:00.123").as_deref(),
+            Some("2024-05-01T21:30:00Z")
+        );
+        for value in ["", "2024-05-01", "yesterday", "2024-05-01 21:30"] {
+            assert_eq!(parse(value), None, "{:?}", value);
+        }
+    }
+}
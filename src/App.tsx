@@ -8,7 +8,7 @@ import { Suspense, useEffect, useState } from "react";
 import { ErrorBoundary } from "react-error-boundary";
 import MoonLoader from "react-spinners/MoonLoader";
 import "./App.css";
-import Draft, { DraftLog } from "./components/draft";
+import Draft, { DraftAction } from "./components/draft";
 import ErrorPage from "./components/ErrorPage";
 import Filter from "./components/filter";
 import { default as LoadingPage } from "./components/LoadingPage";
@@ -74,55 +74,11 @@ function App() {
     retry: true,
   });
 
-  // --- Event Log Query ---
-  const { data: eventLog } = useQuery({
-    queryKey: ["eventLog", selectedGame],
-    queryFn: async () => {
-      const authToken = getAuthToken();
-      if (!authToken) {
-        throw new Error("No auth token, please log in first.");
-      }
-      const url = "https://lol.grid.gg/api/event-explorer-api/events/graphql";
-      const options: RequestInit = {
-        method: "POST",
-        headers: {
-          "Content-Type": "application/json",
-          Authorization: `Bearer ${authToken}`,
-        },
-        body: JSON.stringify({
-          operationName: "getSeriesEvents",
-          variables: {
-            id: selectedGame,
-            filter: {
-              event: [
-                { type: { eq: "team-banned-character" } },
-                { type: { eq: "team-picked-character" } },
-                { type: { eq: "grid-validated-series" } },
-              ],
-            },
-          },
-          query: `query getSeriesEvents($id: String!, $filter: EventsFilter, $after: Cursor) {
-            events(seriesId: $id, filter: $filter, after: $after) {
-              edges {
-                node {
-                  type
-                  sentenceChunks {
-                    text
-                    strikethrough
-                  }
-                }
-              }
-            }
-          }`,
-        }),
-      };
-      const response = await fetch(url, options);
-      if (!response.ok) {
-        throw new Error("Request failed: " + response.statusText);
-      }
-      const data = await response.json();
-      return data.data.events.edges as DraftLog[];
-    },
+  // --- Draft Query ---
+  const { data: draftActions } = useQuery({
+    queryKey: ["draft", selectedGame],
+    queryFn: () =>
+      invoke<DraftAction[]>("get_draft", { seriesId: selectedGame, game: 1 }),
     enabled: !!selectedGame,
     retry: 3,
   });
@@ -279,7 +235,7 @@ function App() {
                       </TabsContent>
                       <TabsContent value="draft" className="h-[95%] w-full">
                         <Draft
-                          eventLog={eventLog ?? []}
+                          draftActions={draftActions ?? []}
                           patch={closestPatch}
                           champions={champions.data}
                         />
@@ -1,149 +1,49 @@
 import { Champion } from "@/lib/types/champions";
 import { ScrollArea } from "./ui/scroll-area";
 
-// Types (adjust the import path as needed)
-export interface Draft {
-  bans1Blue: string[];
-  bans1Red: string[];
-  picks1Blue: string[];
-  picks1Red: string[];
-  picks2Blue: string[];
-  picks2Red: string[];
-  picks3Blue: string[];
-  picks3Red: string[];
-  picks4Blue: string[];
-  picks4Red: string[];
-  bans2Blue: string[];
-  bans2Red: string[];
+// Mirrors `DraftAction` in src-tauri/src/db/models.rs
+export interface DraftAction {
+  id: number;
+  series_id: string;
+  game: number;
+  sequence: number;
+  phase: number;
+  side: "blue" | "red";
+  team_name: string;
+  action: "ban" | "pick";
+  champion_name: string;
 }
 
-export interface DraftLog {
-  node: DraftNode;
-}
-
-export interface DraftNode {
-  type: string;
-  sentenceChunks: SentenceChunk[];
-}
-
-export interface SentenceChunk {
-  text: string;
-  strikethrough: boolean;
-}
-
-const DEFAULT_DRAFT: Draft = {
-  bans1Blue: [],
-  bans1Red: [],
-  picks1Blue: [],
-  picks1Red: [],
-  picks2Blue: [],
-  picks2Red: [],
-  picks3Blue: [],
-  picks3Red: [],
-  picks4Blue: [],
-  picks4Red: [],
-  bans2Blue: [],
-  bans2Red: [],
-};
-
 interface DraftProps {
-  eventLog: DraftLog[];
+  draftActions: DraftAction[];
   champions: Record<string, Champion>;
   patch: string;
 }
 
-function Draft({ eventLog, champions, patch }: DraftProps) {
-  // --- Parse Draft Data ---
-  const parseDraftOrder = (draftLog: DraftLog[]): Draft => {
-    const draft: Draft = {
-      bans1Blue: [],
-      bans1Red: [],
-      picks1Blue: [],
-      picks1Red: [],
-      picks2Blue: [],
-      picks2Red: [],
-      picks3Blue: [],
-      picks3Red: [],
-      picks4Blue: [],
-      picks4Red: [],
-      bans2Blue: [],
-      bans2Red: [],
-    };
-
-    let blueTeam = "";
-    let redTeam = "";
-    const pickOrder = [
-      { team: "blue", picks: 1 },
-      { team: "red", picks: 2 },
-      { team: "blue", picks: 2 },
-      { team: "red", picks: 1 },
-      { team: "red", picks: 1 },
-      { team: "blue", picks: 2 },
-      { team: "red", picks: 1 },
-    ];
-    let pickStage = 0;
-    let bluePickIndex = 1;
-    let redPickIndex = 1;
-
-    draftLog.forEach((e) => {
-      const [team, action, champion] = e.node.sentenceChunks.map(
-        (chunk) => chunk.text
-      );
-
-      if (!blueTeam || !redTeam) {
-        if (!blueTeam) blueTeam = team;
-        else if (team !== blueTeam && !redTeam) redTeam = team;
-      }
-
-      if (action === "banned") {
-        if (team === blueTeam && draft.bans1Blue.length < 3) {
-          draft.bans1Blue.push(champion);
-        } else if (team === redTeam && draft.bans1Red.length < 3) {
-          draft.bans1Red.push(champion);
-        } else if (team === blueTeam) {
-          draft.bans2Blue.push(champion);
-        } else {
-          draft.bans2Red.push(champion);
-        }
-      } else if (action === "picked") {
-        const currentPick = pickOrder[pickStage];
-        if (
-          (team === blueTeam && currentPick.team === "blue") ||
-          (team === redTeam && currentPick.team === "red")
-        ) {
-          if (currentPick.team === "blue") {
-            const key = `picks${bluePickIndex}Blue` as keyof Draft;
-            draft[key].push(champion);
-            if (draft[key].length >= currentPick.picks) {
-              bluePickIndex++;
-              pickStage++;
-            }
-          } else {
-            const key = `picks${redPickIndex}Red` as keyof Draft;
-            draft[key].push(champion);
-            if (draft[key].length >= currentPick.picks) {
-              redPickIndex++;
-              pickStage++;
-            }
-          }
-        }
-      }
-    });
-
-    return draft;
+function Draft({ draftActions, champions, patch }: DraftProps) {
+  // --- Select Draft Data ---
+  const championsFor = (
+    action: DraftAction["action"],
+    phase: number,
+    side: DraftAction["side"]
+  ) =>
+    draftActions
+      .filter(
+        (a) => a.action === action && a.phase === phase && a.side === side
+      )
+      .map((a) => a.champion_name);
+
+  const draftData = {
+    bans1Blue: championsFor("ban", 1, "blue"),
+    bans1Red: championsFor("ban", 1, "red"),
+    picks1Blue: championsFor("pick", 1, "blue"),
+    picks1Red: championsFor("pick", 1, "red"),
+    bans2Blue: championsFor("ban", 2, "blue"),
+    bans2Red: championsFor("ban", 2, "red"),
+    picks2Blue: championsFor("pick", 2, "blue"),
+    picks2Red: championsFor("pick", 2, "red"),
   };
 
-  // --- Compute Draft Data ---
-  let draftData: Draft = DEFAULT_DRAFT;
-  if (eventLog && eventLog.length > 0) {
-    const validatedIndex = eventLog.findIndex(
-      (element) => element.node.type === "grid-validated-series"
-    );
-    const validatedData =
-      validatedIndex >= 0 ? eventLog.slice(validatedIndex + 1) : eventLog;
-    draftData = parseDraftOrder(validatedData);
-  }
-
   // --- Helper: Get Champion Image Filename ---
   const getChampionImage = (championName: string) => {
     const champKey = Object.keys(champions).find(
@@ -195,11 +95,9 @@ function Draft({ eventLog, champions, patch }: DraftProps) {
         <div className="grid grid-cols-2 gap-4 text-center">
           <div className="bg-blue-600 p-4 rounded-lg h-48">
             {renderChampionImages(draftData.picks1Blue, patch)}
-            {renderChampionImages(draftData.picks2Blue, patch)}
           </div>
           <div className="bg-red-600 p-4 rounded-lg flex flex-col justify-end items-end h-48">
             {renderChampionImages(draftData.picks1Red, patch)}
-            {renderChampionImages(draftData.picks2Red, patch)}
           </div>
         </div>
       </div>
@@ -226,11 +124,10 @@ function Draft({ eventLog, champions, patch }: DraftProps) {
         <h3 className="text-xl font-semibold mb-2">Final Pick Phase</h3>
         <div className="grid grid-cols-2 gap-4 text-center">
           <div className="bg-blue-600 p-4 rounded-lg h-36">
-            {renderChampionImages(draftData.picks3Blue, patch)}
+            {renderChampionImages(draftData.picks2Blue, patch)}
           </div>
           <div className="bg-red-600 p-4 rounded-lg flex flex-col items-end justify-end h-36">
-            {renderChampionImages(draftData.picks3Red, patch)}
-            {renderChampionImages(draftData.picks4Red, patch)}
+            {renderChampionImages(draftData.picks2Red, patch)}
           </div>
         </div>
       </div>
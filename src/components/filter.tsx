@@ -6,6 +6,7 @@ import { DatePickerWithRange } from "./ui/daterange";
 
 import { invoke } from "@tauri-apps/api/core";
 import { SearchSelectCommandModes } from "./search-and-select-modes";
+import { Checkbox } from "./ui/checkbox";
 import { Combobox } from "./ui/combobox";
 import {
   Dialog,
@@ -16,18 +17,37 @@ import {
   DialogTrigger,
 } from "./ui/dialog";
 import { Label } from "./ui/label";
+import { RadioGroup, RadioGroupItem } from "./ui/radio-group";
+import { MyTeamPicker } from "./my-team-picker";
+
+interface Matchup {
+  ours?: string | null;
+  theirs?: string | null;
+  role?: "TOP" | "JUNGLE" | "MIDDLE" | "BOTTOM" | "UTILITY" | null;
+}
 
 interface FilterConfig {
   dateRange: DateRange;
   wins: boolean;
   losses: boolean;
+  draws?: boolean; // defaults to true
+  resultScope?: "series" | "game";
   patch: string;
+  patches?: string[]; // e.g. ["14.10", "14.11"]
+  patchRange?: { from?: string | null; to?: string | null };
+  lastPatches?: number | null;
   championsPicked: resultsType[]; // store champion IDs
-  champPickedMode: "Any" | "Only";
+  champPickedMode: "Any" | "Only" | "None";
   championsBanned: resultsType[];
-  champBannedMode: "Any" | "Only";
+  champBannedMode: "Any" | "Only" | "None";
   teams: resultsType[];
   players: resultsType[];
+  tags?: string[];
+  reviewed?: boolean | null;
+  side?: "blue" | "red" | null;
+  matchups?: Matchup[];
+  weekdays?: ("Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun")[];
+  timezone?: string; // "local" (default), "UTC" or an offset such as "+02:00"
 }
 
 // Each champion in DDragon data
@@ -48,14 +68,20 @@ function Filter() {
   });
   const [wins, setWins] = useState(true);
   const [losses, setLosses] = useState(true);
+  const [draws, setDraws] = useState(true);
+  const [resultScope, setResultScope] = useState<"series" | "game">("series");
   const [patch, setPatch] = useState<string>("");
   const [players, setPlayers] = useState<resultsType[]>([]);
   const [teams, setTeams] = useState<resultsType[]>([]);
   const [patches, setPatches] = useState<string[]>([]);
   const [championsPicked, setChampionsPicked] = useState<resultsType[]>([]);
   const [championsBanned, setChampionsBanned] = useState<resultsType[]>([]);
-  const [champPickedMode, setChampPickedMode] = useState<"Any" | "Only">("Any");
-  const [champBannedMode, setChampBannedMode] = useState<"Any" | "Only">("Any");
+  const [champPickedMode, setChampPickedMode] = useState<
+    "Any" | "Only" | "None"
+  >("Any");
+  const [champBannedMode, setChampBannedMode] = useState<
+    "Any" | "Only" | "None"
+  >("Any");
   // Fetch game versions from Data Dragon
   const [isLoadingPatches, setIsLoadingPatches] = useState(true);
 
@@ -187,6 +213,8 @@ function Filter() {
     // Load each field into local state
     setWins(filterConfig.wins);
     setLosses(filterConfig.losses);
+    setDraws(filterConfig.draws ?? true);
+    setResultScope(filterConfig.resultScope ?? "series");
     setPatch(filterConfig.patch);
     setDateRange(filterConfig.dateRange);
     setChampionsPicked(filterConfig.championsPicked || []);
@@ -196,10 +224,10 @@ function Filter() {
   }, []);
 
   // ----------------------------------------------------------------
-  // Example usage with ShadCN <Checkbox> you might want to store these changes in state
-  // So that we can reflect them in localStorage on "Save changes"
-  // const handleToggleWins = () => setWins((prev) => !prev);
-  // const handleToggleLosses = () => setLosses((prev) => !prev);
+  // Result checkboxes, saved to localStorage when the dialog closes
+  const handleToggleWins = () => setWins((prev) => !prev);
+  const handleToggleLosses = () => setLosses((prev) => !prev);
+  const handleToggleDraws = () => setDraws((prev) => !prev);
 
   // We'll do something similar for patch input
   const handlePatchChange = (e: string) => {
@@ -213,6 +241,8 @@ function Filter() {
       dateRange,
       wins,
       losses,
+      draws,
+      resultScope,
       patch,
       championsPicked,
       champPickedMode,
@@ -249,6 +279,12 @@ function Filter() {
         </DialogHeader>
 
         <div className="grid gap-4 py-4">
+          {/* My Team: needed for results, sides and picks by us */}
+          <div className="grid grid-cols-4 items-start gap-4">
+            <Label className="text-right">My Team</Label>
+            <MyTeamPicker />
+          </div>
+
           {/* Date Range */}
           <div className="grid grid-cols-4 items-center gap-4">
             <Label htmlFor="datepicker" className="text-right">
@@ -263,8 +299,8 @@ function Filter() {
             />
           </div>
 
-          {/* Wins / Losses */}
-          {/* <div className="grid grid-cols-4 gap-4 items-center">
+          {/* Wins / Losses / Draws */}
+          <div className="grid grid-cols-4 gap-4 items-center">
             <Label htmlFor="wins" className="text-right">
               Wins
             </Label>
@@ -283,7 +319,35 @@ function Filter() {
               className="col-span-3"
               onCheckedChange={handleToggleLosses}
             />
-          </div> */}
+            <Label htmlFor="draws" className="text-right">
+              Draws
+            </Label>
+            <Checkbox
+              id="draws"
+              checked={draws}
+              disabled={resultScope === "game"}
+              className="col-span-3"
+              onCheckedChange={handleToggleDraws}
+            />
+            {/* Games can't be drawn, so draws only count by series */}
+            <Label className="text-right">Count</Label>
+            <RadioGroup
+              value={resultScope}
+              onValueChange={(value) =>
+                setResultScope(value as "series" | "game")
+              }
+              className="col-span-3 flex gap-4"
+            >
+              <div className="flex items-center gap-2">
+                <RadioGroupItem value="series" id="scope-series" />
+                <Label htmlFor="scope-series">Series</Label>
+              </div>
+              <div className="flex items-center gap-2">
+                <RadioGroupItem value="game" id="scope-game" />
+                <Label htmlFor="scope-game">Games</Label>
+              </div>
+            </RadioGroup>
+          </div>
 
           {/* Patch Input */}
           <div className="grid grid-cols-4 gap-4 items-center">
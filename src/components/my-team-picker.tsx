@@ -0,0 +1,77 @@
+import { MyTeamCandidates } from "@/lib/types/types";
+import { getAuthToken } from "@/lib/utils";
+import { useQueryClient } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+import { Checkbox } from "./ui/checkbox";
+import { Label } from "./ui/label";
+
+/**
+ * Picks which GRID teams are "us" when the organisation name matches several.
+ * Changes are saved right away and the sidebar reloads with them.
+ */
+export function MyTeamPicker() {
+  const queryClient = useQueryClient();
+  const [found, setFound] = useState<MyTeamCandidates | null>(null);
+  const [selected, setSelected] = useState<string[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<MyTeamCandidates>("get_my_team_candidates", {
+      authToken: getAuthToken(),
+    })
+      .then((candidates) => {
+        setFound(candidates);
+        setSelected(candidates.selected);
+      })
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  async function toggle(teamId: string, checked: boolean) {
+    const teamIds = checked
+      ? [...selected, teamId]
+      : selected.filter((id) => id !== teamId);
+    try {
+      await invoke("set_my_team", {
+        teamIds,
+        orgName: found?.org_name ?? null,
+      });
+      setSelected(teamIds);
+      setError(null);
+      queryClient.invalidateQueries({ queryKey: ["SidebarLoader"] });
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  if (error) {
+    return <div className="col-span-3 text-sm text-red-500">{error}</div>;
+  }
+  if (!found) {
+    return <div className="col-span-3 text-sm">Loading teams...</div>;
+  }
+  if (found.candidates.length === 0) {
+    return (
+      <div className="col-span-3 text-sm">
+        No teams match the name '{found.org_name}'.
+      </div>
+    );
+  }
+  return (
+    <div className="col-span-3 grid gap-2">
+      {found.candidates.map((team) => (
+        <div key={team.id} className="flex items-center gap-2">
+          <Checkbox
+            id={`my-team-${team.id}`}
+            checked={selected.includes(team.id)}
+            onCheckedChange={(checked) => toggle(team.id, checked === true)}
+          />
+          {team.logoUrl && (
+            <img src={team.logoUrl} className="h-5 w-5" alt="" />
+          )}
+          <Label htmlFor={`my-team-${team.id}`}>{team.name}</Label>
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -21,8 +21,10 @@ export type resultsType = { label: string; value: string; logoUrl?: string };
 export interface SearchSelectCommandModesProps {
   fetchFn: (searchTerm: string) => Promise<resultsType[]>;
   selected: resultsType[];
-  selectMode: "Any" | "Only";
-  setSelectMode: React.Dispatch<React.SetStateAction<"Any" | "Only">>;
+  selectMode: "Any" | "Only" | "None";
+  setSelectMode: React.Dispatch<
+    React.SetStateAction<"Any" | "Only" | "None">
+  >;
   setSelected: React.Dispatch<React.SetStateAction<resultsType[]>>;
   placeholder?: string;
   label?: string;
@@ -96,7 +98,7 @@ export const SearchSelectCommandModes: React.FC<
                   </div>
                   <div
                     className={cn(
-                      "flex-1 text-center py-2 text-sm font-medium cursor-pointer rounded-tr-md ",
+                      "flex-1 text-center py-2 text-sm font-medium cursor-pointer",
                       selectMode === "Only"
                         ? "bg-primary text-white"
                         : "hover:bg-gray-100"
@@ -108,6 +110,20 @@ export const SearchSelectCommandModes: React.FC<
                   >
                     Only
                   </div>
+                  <div
+                    className={cn(
+                      "flex-1 text-center py-2 text-sm font-medium cursor-pointer rounded-tr-md ",
+                      selectMode === "None"
+                        ? "bg-primary text-white"
+                        : "hover:bg-gray-100"
+                    )}
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      setSelectMode("None");
+                    }}
+                  >
+                    None
+                  </div>
                 </div>
                 <div className="p-4">
                   <div className="flex flex-wrap gap-2 justify-start items-center">
@@ -128,7 +144,7 @@ export const SearchSelectCommandModes: React.FC<
                         </Badge>
                         {index < selected.length - 1 && (
                           <span className="text-xs font-medium px-2 self-center">
-                            {selectMode === "Any" ? "or" : "and"}
+                            {selectMode === "Only" ? "and" : "or"}
                           </span>
                         )}
                       </React.Fragment>
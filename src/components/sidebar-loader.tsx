@@ -3,8 +3,9 @@ import {
   DetailedSeries,
   PageInfo,
   Participant,
+  FilterDiagnostics,
   SeriesEdge,
-  SeriesWithParticipants,
+  SeriesResults,
 } from "@/lib/types/types";
 import { getAuthToken } from "@/lib/utils";
 import {
@@ -79,14 +80,14 @@ function SidebarLoader({
    */
   async function queryFn(
     ctx: QueryFunctionContext<["SidebarLoader", FilterConfig | null]>
-  ): Promise<SeriesWithParticipants[]> {
+  ): Promise<SeriesResults> {
     const [, currentFilters] = ctx.queryKey;
     const authToken = getAuthToken();
 
     if (!authToken) throw new Error("No auth token, please log in first.");
 
     if (!currentFilters) {
-      return [];
+      return { series: [] };
     }
 
     return await invoke("get_series_with_participants", {
@@ -126,13 +127,24 @@ function SidebarLoader({
     queryKey: ["startSync", data],
     queryFn: start_sync,
     // Only run this if filters are set and the main data returns empty.
-    enabled: data !== undefined && data.length === 0,
+    enabled: data !== undefined && data.series.length === 0,
     retry: false,
   });
 
   return (
     <>
-      {data.map((edge) => {
+      {data.diagnostics?.my_team_error && (
+        <div className="w-full p-4 text-center text-sm text-red-500">
+          {data.diagnostics.my_team_error} Until then, filters on results,
+          sides and picks by us can't tell which team is yours.
+        </div>
+      )}
+      {data.series.length === 0 && (
+        <div className="w-full p-4 text-center text-accent">
+          {emptyReason(data.diagnostics)}
+        </div>
+      )}
+      {data.series.map((edge) => {
         const series = edge.series;
         const series_id = series.series_id;
         const team1Score = series.team1_score || 0;
@@ -185,4 +197,23 @@ function SidebarLoader({
   );
 }
 
+/** Why the list is empty: nothing synced yet, or the first filter that left no series. */
+function emptyReason(diagnostics?: FilterDiagnostics): string {
+  if (!diagnostics) {
+    return "No Series Found.";
+  }
+  if (diagnostics.total === 0) {
+    return "No series synced yet.";
+  }
+  const stage = diagnostics.stages.find((stage) => stage.remaining === 0);
+  if (!stage) {
+    return "No Series Found.";
+  }
+  if (stage.stage === "known_sides") {
+    return `No Series Found: games can only be counted once their sides are known from the draft, and none of the ${stage.excluded} series left have that.`;
+  }
+  const name = stage.stage.replace(/_/g, " ");
+  return `No Series Found: the ${name} filter excluded the last ${stage.excluded} of ${diagnostics.total} series.`;
+}
+
 export default SidebarLoader;
@@ -8,6 +8,9 @@ export interface DateRange {
 export interface ChampionSelection {
   value: string;
   label: string;
+  role?: "TOP" | "JUNGLE" | "MIDDLE" | "BOTTOM" | "UTILITY";
+  team?: "us" | "opponent";
+  phase?: 1 | 2; // bans only
 }
 
 export interface TeamFilter {
@@ -8,7 +8,35 @@ export interface Participant {
     player_name: string;
     champion_name: string;
     stats_json: string;
+    game: number;
+    kills: number | null;
+    deaths: number | null;
+    assists: number | null;
+    total_minions_killed: number | null;
+    neutral_minions_killed: number | null;
+    gold_earned: number | null;
+    total_damage_dealt_to_champions: number | null;
+    total_damage_taken: number | null;
+    vision_score: number | null;
+    wards_placed: number | null;
+    wards_killed: number | null;
+    detector_wards_placed: number | null;
+    item0: number | null;
+    item1: number | null;
+    item2: number | null;
+    item3: number | null;
+    item4: number | null;
+    item5: number | null;
+    item6: number | null;
+    primary_rune_style: number | null;
+    keystone: number | null;
+    secondary_rune_style: number | null;
+    team_position: string | null;
+    team_id: number | null;
+    win: boolean | null;
+    role: Role | null;
 }
+export type Role = "TOP" | "JUNGLE" | "MIDDLE" | "BOTTOM" | "UTILITY";
 
 export interface Series {
     id: number;
@@ -24,11 +52,74 @@ export interface Series {
     team2_name: string | null;
     team2_score: number | null;
     team2_logo: string | null;
+    patch_major: number | null;
+    patch_minor: number | null;
 }
 
+export interface Annotation {
+    id: number;
+    series_id: string;
+    game: number; // 0 for the whole series
+    note: string;
+    reviewed: boolean;
+    created_at: string;
+    updated_at: string;
+    tags: string[];
+}
 export interface SeriesWithParticipants {
     series: Series;
     participants: Participant[];
+    games: Game[];
+    annotations: Annotation[];
+}
+export type Side = "blue" | "red";
+export interface Game {
+    id: number;
+    series_id: string;
+    game: number;
+    duration_seconds: number | null;
+    blue_team_id: string | null;
+    red_team_id: string | null;
+    winning_side: Side | null;
+}
+export type SortKey = "date" | "duration" | "opponent" | "result";
+export interface SortOrder {
+    key: SortKey;
+    descending: boolean;
+}
+export interface SeriesListItem extends Series {
+    duration_seconds: number | null;
+    games: number;
+}
+export interface SeriesPage {
+    items: SeriesListItem[];
+    next_cursor: string | null;
+    total: number;
+    diagnostics?: FilterDiagnostics;
+}
+export interface SeriesResults {
+    series: SeriesWithParticipants[];
+    diagnostics?: FilterDiagnostics;
+}
+export interface StageDiagnostic {
+    stage: string;
+    excluded: number;
+    remaining: number;
+}
+export interface FilterDiagnostics {
+    total: number;
+    stages: StageDiagnostic[];
+    my_team_error?: string; // my team couldn't be determined, so team filters used none
+}
+export interface TeamInfoShort {
+    id: string;
+    name: string;
+    logoUrl: string;
+}
+export interface MyTeamCandidates {
+    org_name: string;
+    candidates: TeamInfoShort[];
+    selected: string[]; // team ids saved as mine
 }
 export interface Player {
     id: string;
@@ -113,3 +204,10 @@ export interface DetailedSeries {
 }
 
 
+export interface FilterPreset<Filters = unknown> {
+    id: number;
+    name: string;
+    filters: Filters;
+    created_at: string;
+    updated_at: string;
+}